@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+use spew::prelude::*;
+
+#[derive(Debug, Eq, PartialEq)]
+enum Object {
+    Tree,
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(SpewPlugin::<Object, Transform>::default())
+        .add_spawner((Object::Tree, spawn_tree))
+        // Handles all trees of a `SpawnBatchEvent` in one go
+        .add_spawner((Object::Tree, batch(spawn_trees)))
+        .add_systems(Startup, plant_forest)
+        .add_systems(Update, count_trees)
+        .run();
+}
+
+fn plant_forest(mut spawn_events: EventWriter<SpawnBatchEvent<Object, Transform>>) {
+    let positions = (0..500).map(|i| Transform::from_xyz((i % 25) as f32, 0.0, (i / 25) as f32));
+    spawn_events.send(SpawnBatchEvent::new(Object::Tree, positions));
+}
+
+// Used for single `SpawnEvent`s
+fn spawn_tree(In(transform): In<Transform>, mut commands: Commands) {
+    commands.spawn((Name::new("Tree"), transform));
+}
+
+fn spawn_trees(In(transforms): In<Vec<Transform>>, mut commands: Commands) {
+    commands.spawn_batch(
+        transforms
+            .into_iter()
+            .map(|transform| (Name::new("Tree"), transform)),
+    );
+}
+
+fn count_trees(trees: Query<&Spewed<Object>, Added<Spewed<Object>>>) {
+    let count = trees.iter().count();
+    if count > 0 {
+        info!("Planted {count} trees");
+    }
+}
@@ -0,0 +1,61 @@
+use bevy::prelude::*;
+use spew::prelude::*;
+
+#[derive(Debug, Eq, PartialEq)]
+enum Object {
+    Minion,
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(SpewPlugin::<Object>::default())
+        .add_spawner((Object::Minion, spawn_minion))
+        .add_systems(Startup, summon_minions)
+        .add_systems(Update, (list_pending_minions, kill_boss))
+        .run();
+}
+
+#[derive(Resource)]
+struct SummonedMinions(Vec<SpawnHandle>);
+
+fn summon_minions(mut commands: Commands) {
+    let handles = (1..=5)
+        .map(|seconds| {
+            commands
+                .spew_event(SpawnEvent::<Object>::new(Object::Minion).delay_seconds(seconds as f32))
+        })
+        .collect();
+    commands.insert_resource(SummonedMinions(handles));
+}
+
+fn list_pending_minions(pending_spawns: Res<PendingSpawns<Object>>, mut last_len: Local<usize>) {
+    if pending_spawns.len() != *last_len {
+        *last_len = pending_spawns.len();
+        info!("{} minions are still on their way", pending_spawns.len());
+    }
+}
+
+fn kill_boss(
+    time: Res<Time>,
+    minions: Option<Res<SummonedMinions>>,
+    mut cancel_events: EventWriter<CancelSpawn>,
+    mut commands: Commands,
+) {
+    let Some(minions) = minions else {
+        return;
+    };
+    if time.elapsed_seconds() < 2.5 {
+        return;
+    }
+    info!("The boss died, so no more minions will appear");
+    for &handle in &minions.0 {
+        cancel_events.send(CancelSpawn(handle));
+    }
+    commands.remove_resource::<SummonedMinions>();
+}
+
+fn spawn_minion(mut commands: Commands) {
+    info!("Spawning minion");
+    commands.spawn(Name::new("Minion"));
+}
@@ -0,0 +1,40 @@
+use bevy::prelude::*;
+use spew::prelude::*;
+
+#[derive(Debug, Eq, PartialEq)]
+enum Object {
+    Tree,
+    Apple,
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(SpewPlugin::<Object, Transform>::default())
+        .add_spawners(((Object::Tree, spawn_tree), (Object::Apple, spawn_apple)))
+        .add_systems(Startup, plant_tree)
+        .run();
+}
+
+fn plant_tree(mut commands: Commands) {
+    // The tree is spawned as soon as the commands of this system are applied
+    commands.spew(Object::Tree, Transform::default());
+}
+
+fn spawn_tree(In(transform): In<Transform>, mut commands: Commands) {
+    info!("Spawning tree at {}", transform.translation);
+    commands.spawn((Name::new("Tree"), transform));
+
+    // Spawners can spawn other objects without waiting for the next frame
+    for x in [-1.0, 1.0] {
+        commands.spew(
+            Object::Apple,
+            transform.with_translation(transform.translation + Vec3::new(x, 2.0, 0.0)),
+        );
+    }
+}
+
+fn spawn_apple(In(transform): In<Transform>, mut commands: Commands) {
+    info!("Spawning apple at {}", transform.translation);
+    commands.spawn((Name::new("Apple"), transform));
+}
@@ -1,6 +1,7 @@
 use bevy::core::FrameCount;
 use bevy::prelude::*;
 use spew::prelude::*;
+use std::time::Duration;
 
 #[derive(Debug, Eq, PartialEq)]
 enum Object {
@@ -25,6 +26,12 @@ fn spawn_various_delays(mut spawn_events: EventWriter<SpawnEvent<Object>>) {
 
     // This cube will spawn after 0.5
     spawn_events.send(SpawnEvent::new(Object::Cube).delay_seconds(0.5));
+
+    // This cube will spawn on frame 100
+    spawn_events.send(SpawnEvent::new(Object::Cube).at_frame(100));
+
+    // This cube will spawn once the app has been running for 2.5 seconds
+    spawn_events.send(SpawnEvent::new(Object::Cube).at_elapsed(Duration::from_secs_f32(2.5)));
 }
 
 fn spawn_cube(mut commands: Commands, frame_count: Res<FrameCount>, time: Res<Time>) {
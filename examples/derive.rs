@@ -0,0 +1,46 @@
+use bevy::prelude::*;
+use spew::prelude::*;
+
+// Every variant needs a spawner, so forgetting one is a compile error
+#[derive(Debug, Eq, PartialEq, Spewable)]
+enum Object {
+    #[spawner(spawn_cube)]
+    Cube,
+    #[spawner(spawn_triangle)]
+    Triangle,
+    #[spawner(spawn_sphere)]
+    Sphere,
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(SpewPlugin::<Object, Transform>::default())
+        .add_spewable::<Object>()
+        .add_systems(Startup, spawn_things)
+        .run();
+}
+
+fn spawn_things(mut spawn_events: EventWriter<SpawnEvent<Object, Transform>>) {
+    spawn_events.send(SpawnEvent::with_data(Object::Cube, Transform::default()));
+    spawn_events.send(SpawnEvent::with_data(
+        Object::Triangle,
+        Transform::default(),
+    ));
+    spawn_events.send(SpawnEvent::with_data(Object::Sphere, Transform::default()));
+}
+
+fn spawn_cube(In(transform): In<Transform>, mut commands: Commands) {
+    info!("Spawning cube at {}", transform.translation);
+    commands.spawn((Name::new("Cube"), transform));
+}
+
+fn spawn_triangle(In(transform): In<Transform>, mut commands: Commands) {
+    info!("Spawning triangle at {}", transform.translation);
+    commands.spawn((Name::new("Triangle"), transform));
+}
+
+fn spawn_sphere(In(transform): In<Transform>, mut commands: Commands) {
+    info!("Spawning sphere at {}", transform.translation);
+    commands.spawn((Name::new("Sphere"), transform));
+}
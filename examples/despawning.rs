@@ -0,0 +1,59 @@
+use bevy::prelude::*;
+use spew::prelude::*;
+
+#[derive(Debug, Eq, PartialEq)]
+enum Object {
+    Monster,
+    Coin,
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(SpewPlugin::<Object, Transform>::default())
+        .add_spawners(((Object::Monster, spawn_monster), (Object::Coin, spawn_coin)))
+        // Runs right before a monster is despawned
+        .add_despawner((Object::Monster, drop_coin))
+        .add_systems(Startup, spawn_monsters)
+        .add_systems(Update, kill_monsters)
+        .run();
+}
+
+fn spawn_monsters(mut spawn_events: EventWriter<SpawnEvent<Object, Transform>>) {
+    for x in 0..3 {
+        spawn_events.send(SpawnEvent::with_data(
+            Object::Monster,
+            Transform::from_xyz(x as f32, 0.0, 0.0),
+        ));
+    }
+}
+
+fn spawn_monster(In(transform): In<Transform>, mut commands: Commands) {
+    commands.spawn((Name::new("Monster"), transform));
+}
+
+fn spawn_coin(In(transform): In<Transform>, mut commands: Commands) {
+    commands.spawn((Name::new("Coin"), transform));
+}
+
+fn drop_coin(In(entity): In<Entity>, transforms: Query<&Transform>, mut commands: Commands) {
+    let transform = transforms.get(entity).copied().unwrap_or_default();
+    info!("Monster {entity} dropped a coin");
+    commands.spew(Object::Coin, transform);
+}
+
+// Spew inserts `Spewed<Object>` on everything it spawned, so we can tell monsters apart from coins
+fn kill_monsters(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    spewed: Query<(Entity, &Spewed<Object>)>,
+    mut despawn_events: EventWriter<DespawnEvent<Object>>,
+) {
+    if !keyboard.just_pressed(KeyCode::Space) {
+        return;
+    }
+    for (entity, spewed) in &spewed {
+        if spewed.is(&Object::Monster) {
+            despawn_events.send(DespawnEvent::new(entity));
+        }
+    }
+}
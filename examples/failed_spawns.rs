@@ -0,0 +1,57 @@
+use bevy::prelude::*;
+use spew::prelude::*;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+enum Object {
+    Monster,
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        // Failed spawns are tried again in the next frame, up to three times
+        .add_plugins(
+            SpewPlugin::<Object, Transform>::default()
+                .failed_spawns(RetryPolicy::NextFrame { max_retries: 3 }),
+        )
+        .add_spawner((Object::Monster, spawn_monster))
+        .add_systems(Startup, request_monster)
+        .add_systems(Update, (add_spawn_point, report_failed_spawns))
+        .run();
+}
+
+#[derive(Component)]
+struct SpawnPoint;
+
+fn request_monster(mut spawn_events: EventWriter<SpawnEvent<Object, Transform>>) {
+    spawn_events.send(SpawnEvent::with_data(Object::Monster, Transform::default()));
+}
+
+// The spawn point only shows up after a couple of frames, so the first attempts fail
+fn add_spawn_point(mut commands: Commands, mut frames: Local<u32>) {
+    *frames += 1;
+    if *frames == 3 {
+        commands.spawn((SpawnPoint, Transform::from_xyz(5.0, 0.0, 0.0)));
+    }
+}
+
+fn spawn_monster(
+    In(offset): In<Transform>,
+    spawn_points: Query<&Transform, With<SpawnPoint>>,
+    mut commands: Commands,
+) -> Result<Entity, &'static str> {
+    let spawn_point = spawn_points.get_single().map_err(|_| "no spawn point")?;
+    info!("Spawning a monster");
+    Ok(commands
+        .spawn((Name::new("Monster"), spawn_point.mul_transform(offset)))
+        .id())
+}
+
+fn report_failed_spawns(mut failed_events: EventReader<SpawnFailed<Object, Transform>>) {
+    for event in failed_events.read() {
+        warn!(
+            "Failed to spawn {:?} (attempt {}): {}. Retrying: {}",
+            event.object, event.attempt, event.error, event.retrying
+        );
+    }
+}
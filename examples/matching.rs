@@ -0,0 +1,58 @@
+use bevy::prelude::*;
+use spew::prelude::*;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+enum Enemy {
+    Goblin { level: u8 },
+    Dragon,
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(SpewPlugin::<Enemy, Transform>::default())
+        .add_spawners((
+            // Spawners for an exact object take precedence over matchers
+            (Enemy::Dragon, spawn_dragon),
+            // Matchers are tried in the order they were registered, so put the more specific ones first
+            (
+                spew::matches!(Enemy::Goblin { level } if *level >= 10),
+                spawn_goblin_chief,
+            ),
+            (spew::matches!(Enemy::Goblin { .. }), spawn_goblin),
+        ))
+        .add_systems(Startup, spawn_enemies)
+        .run();
+}
+
+fn spawn_enemies(mut spawn_events: EventWriter<SpawnEvent<Enemy, Transform>>) {
+    for level in [1, 5, 12] {
+        spawn_events.send(SpawnEvent::with_data(
+            Enemy::Goblin { level },
+            Transform::from_xyz(level as f32, 0.0, 0.0),
+        ));
+    }
+    spawn_events.send(SpawnEvent::with_data(Enemy::Dragon, Transform::default()));
+}
+
+fn spawn_dragon(In(transform): In<Transform>, mut commands: Commands) {
+    info!("Spawning dragon at {}", transform.translation);
+    commands.spawn((Name::new("Dragon"), transform));
+}
+
+// Spawners registered with a matcher also receive the object that was requested
+fn spawn_goblin(In((enemy, transform)): In<(Enemy, Transform)>, mut commands: Commands) {
+    let Enemy::Goblin { level } = enemy else {
+        return;
+    };
+    info!("Spawning level {level} goblin at {}", transform.translation);
+    commands.spawn((Name::new(format!("Goblin (level {level})")), transform));
+}
+
+fn spawn_goblin_chief(In((enemy, transform)): In<(Enemy, Transform)>, mut commands: Commands) {
+    info!(
+        "Spawning goblin chief {enemy:?} at {}",
+        transform.translation
+    );
+    commands.spawn((Name::new("Goblin Chief"), transform));
+}
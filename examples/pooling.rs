@@ -0,0 +1,83 @@
+use bevy::prelude::*;
+use spew::prelude::*;
+
+#[derive(Debug, Eq, PartialEq)]
+enum Projectile {
+    Bullet,
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(SpewPlugin::<Projectile, Transform>::default())
+        .add_spawner((Projectile::Bullet, spawn_bullet))
+        // Bullets are reused instead of being spawned and despawned over and over again
+        .add_pool(
+            Projectile::Bullet,
+            reset_bullet,
+            PoolConfig::default()
+                .prewarm(16)
+                .max_size(64)
+                .overflow(PoolOverflow::RecycleOldest),
+        )
+        .add_systems(Update, (fire_bullets, move_bullets, recycle_bullets))
+        .run();
+}
+
+#[derive(Component)]
+struct Bullet {
+    lifetime: Timer,
+}
+
+fn fire_bullets(mut spawn_events: EventWriter<SpawnEvent<Projectile, Transform>>) {
+    spawn_events.send(SpawnEvent::with_data(
+        Projectile::Bullet,
+        Transform::default(),
+    ));
+}
+
+// Only runs when the pool has to grow
+fn spawn_bullet(In(transform): In<Transform>, mut commands: Commands) -> Entity {
+    info!("Spawning a new bullet");
+    commands
+        .spawn((
+            Name::new("Bullet"),
+            Bullet {
+                lifetime: Timer::from_seconds(0.5, TimerMode::Once),
+            },
+            transform,
+        ))
+        .id()
+}
+
+// Runs instead of `spawn_bullet` when a pooled bullet is available
+fn reset_bullet(
+    In((entity, transform)): In<(Entity, Transform)>,
+    mut bullets: Query<(&mut Bullet, &mut Transform)>,
+) {
+    let (mut bullet, mut bullet_transform) = bullets.get_mut(entity).unwrap();
+    bullet.lifetime.reset();
+    *bullet_transform = transform;
+}
+
+// Pooled bullets stay in the world, so skip the ones waiting in the pool
+fn move_bullets(
+    time: Res<Time>,
+    mut bullets: Query<&mut Transform, (With<Bullet>, Without<InPool>)>,
+) {
+    for mut transform in &mut bullets {
+        transform.translation.x += 100.0 * time.delta_seconds();
+    }
+}
+
+fn recycle_bullets(
+    time: Res<Time>,
+    mut bullets: Query<(Entity, &mut Bullet), Without<InPool>>,
+    mut commands: Commands,
+) {
+    for (entity, mut bullet) in &mut bullets {
+        if bullet.lifetime.tick(time.delta()).just_finished() {
+            commands.recycle(entity);
+        }
+    }
+}
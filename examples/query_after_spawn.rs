@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 use spew::prelude::*;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 enum Object {
     Player,
 }
@@ -9,7 +9,7 @@ enum Object {
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
-        .add_plugins(SpewPlugin::<Object>::default())
+        .add_plugins(SpewPlugin::<Object>::default().track_origin())
         .add_spawner((Object::Player, spawn_player))
         .add_systems(Startup, setup)
         .add_systems(Update, query_player.after(SpewSystemSet))
@@ -31,7 +31,11 @@ fn setup(mut spawn_events: EventWriter<SpawnEvent<Object>>) {
     spawn_events.send(SpawnEvent::new(Object::Player));
 }
 
-fn query_player(player: Query<&Player>) {
-    let player = player.single();
-    info!("Found a player named {}", player.name);
+// Spew inserts `Spewed<Object>` on the spawned entity, which tells us where it came from
+fn query_player(player: Query<(&Player, &Spewed<Object>)>) {
+    let (player, spewed) = player.single();
+    info!(
+        "Found a player named {}, spawned as {:?} in frame {}",
+        player.name, spewed.object, spewed.frame
+    );
 }
@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+use spew::prelude::*;
+
+#[derive(Debug, Eq, PartialEq)]
+enum Object {
+    Cube,
+}
+
+const LEADER_ID: u64 = 1;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(SpewPlugin::<Object>::default())
+        .add_spawner((Object::Cube, spawn_cube))
+        .add_systems(Startup, spawn_cubes)
+        .add_systems(Update, mark_leader.after(SpewSystemSet))
+        .run();
+}
+
+#[derive(Component)]
+struct Leader;
+
+fn spawn_cubes(mut spawn_events: EventWriter<SpawnEvent<Object>>) {
+    spawn_events.send(SpawnEvent::new(Object::Cube));
+    spawn_events.send(SpawnEvent::new(Object::Cube).correlation_id(LEADER_ID));
+    spawn_events.send(SpawnEvent::new(Object::Cube));
+}
+
+// Returning the entity lets spew report it in a `SpawnedEvent`
+fn spawn_cube(mut commands: Commands) -> Entity {
+    commands.spawn(Name::new("Cube")).id()
+}
+
+fn mark_leader(mut spawned_events: EventReader<SpawnedEvent<Object>>, mut commands: Commands) {
+    for event in spawned_events.read() {
+        if event.correlation_id != Some(LEADER_ID) {
+            continue;
+        }
+        for &entity in &event.entities {
+            info!("Cube {entity} is the leader");
+            commands.entity(entity).insert(Leader);
+        }
+    }
+}
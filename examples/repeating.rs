@@ -0,0 +1,67 @@
+use bevy::prelude::*;
+use spew::prelude::*;
+use std::time::Duration;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+enum Object {
+    Enemy,
+    Coin,
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(SpewPlugin::<Object, Transform>::default())
+        .add_spawners(((Object::Enemy, spawn_enemy), (Object::Coin, spawn_coin)))
+        .add_systems(Startup, setup_spawns)
+        .add_systems(Update, toggle_enemy_spawning)
+        .run();
+}
+
+#[derive(Resource)]
+struct EnemySpawning(SpawnHandle);
+
+fn setup_spawns(
+    mut spawn_events: EventWriter<SpawnEvent<Object, Transform>>,
+    mut commands: Commands,
+) {
+    // Spawns an enemy every 2 seconds until the spawn is cancelled
+    let enemies = SpawnEvent::with_data(Object::Enemy, Transform::from_xyz(0.0, 0.0, 0.0))
+        .repeat_forever(Interval::Seconds(Duration::from_secs(2)));
+    commands.insert_resource(EnemySpawning(enemies.handle));
+    spawn_events.send(enemies);
+
+    // Spawns a coin now and 4 more every 30 frames
+    spawn_events.send(
+        SpawnEvent::with_data(Object::Coin, Transform::from_xyz(5.0, 0.0, 0.0))
+            .repeat(4, Interval::Frames(30)),
+    );
+}
+
+fn toggle_enemy_spawning(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    enemy_spawning: Res<EnemySpawning>,
+    pending_spawns: Res<PendingSpawns<Object, Transform>>,
+    mut commands: Commands,
+) {
+    if !keyboard.just_pressed(KeyCode::Space) {
+        return;
+    }
+    if pending_spawns.is_paused(enemy_spawning.0) {
+        info!("Resuming enemy spawns");
+        commands.resume_spawn(enemy_spawning.0);
+    } else {
+        info!("Pausing enemy spawns");
+        commands.pause_spawn(enemy_spawning.0);
+    }
+}
+
+fn spawn_enemy(In(transform): In<Transform>, mut commands: Commands) {
+    info!("Spawning enemy");
+    commands.spawn((Name::new("Enemy"), transform));
+}
+
+fn spawn_coin(In(transform): In<Transform>, mut commands: Commands) {
+    info!("Spawning coin");
+    commands.spawn((Name::new("Coin"), transform));
+}
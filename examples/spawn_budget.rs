@@ -0,0 +1,36 @@
+use bevy::diagnostic::LogDiagnosticsPlugin;
+use bevy::prelude::*;
+use spew::prelude::*;
+use std::time::Duration;
+
+#[derive(Debug, Eq, PartialEq)]
+enum Object {
+    Particle,
+}
+
+fn main() {
+    let backlog = SpewPlugin::<Object, Transform>::backlog_diagnostic();
+    App::new()
+        .add_plugins(DefaultPlugins)
+        // Logs the size of the backlog every second
+        .add_plugins(LogDiagnosticsPlugin::filtered(vec![backlog]))
+        // Spend at most 2 milliseconds per frame on spawning particles, the rest is spawned in the following frames
+        .add_plugins(
+            SpewPlugin::<Object, Transform>::default()
+                .spawn_budget(SpawnBudget::Time(Duration::from_millis(2))),
+        )
+        .add_spawner((Object::Particle, spawn_particle))
+        .add_systems(Startup, explode)
+        .run();
+}
+
+fn explode(mut spawn_events: EventWriter<SpawnEvent<Object, Transform>>) {
+    let positions =
+        (0..10_000).map(|i| Transform::from_xyz((i % 100) as f32, 0.0, (i / 100) as f32));
+    spawn_events
+        .send_batch(positions.map(|transform| SpawnEvent::with_data(Object::Particle, transform)));
+}
+
+fn spawn_particle(In(transform): In<Transform>, mut commands: Commands) {
+    commands.spawn((Name::new("Particle"), transform));
+}
@@ -0,0 +1,45 @@
+use bevy::prelude::*;
+use spew::prelude::*;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+enum Furniture {
+    Chair,
+    Table,
+    Bed,
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(SpewPlugin::<Furniture, Transform>::default())
+        // A single function handles all furniture, since the context tells it what to spawn
+        .add_spawners((
+            (Furniture::Chair, with_context(spawn_furniture)),
+            (Furniture::Table, with_context(spawn_furniture)),
+            (Furniture::Bed, with_context(spawn_furniture)),
+        ))
+        .add_systems(Startup, setup_room)
+        .run();
+}
+
+fn setup_room(mut spawn_events: EventWriter<SpawnEvent<Furniture, Transform>>) {
+    spawn_events.send(SpawnEvent::with_data(
+        Furniture::Chair,
+        Transform::from_xyz(1.0, 0.0, 0.0),
+    ));
+    spawn_events.send(
+        SpawnEvent::with_data(Furniture::Table, Transform::from_xyz(2.0, 0.0, 0.0))
+            .correlation_id(42),
+    );
+    spawn_events.send(
+        SpawnEvent::with_data(Furniture::Bed, Transform::from_xyz(3.0, 0.0, 0.0)).delay_frames(10),
+    );
+}
+
+fn spawn_furniture(In(context): In<SpawnContext<Furniture, Transform>>, mut commands: Commands) {
+    info!(
+        "Spawning {:?} at {} (requested in frame {}, correlation id {:?})",
+        context.object, context.data.translation, context.frame, context.correlation_id
+    );
+    commands.spawn((Name::new(format!("{:?}", context.object)), context.data));
+}
@@ -0,0 +1,82 @@
+use bevy::prelude::*;
+use spew::prelude::*;
+use std::time::Duration;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+enum Enemy {
+    Zombie,
+    Boss,
+}
+
+#[derive(Component)]
+struct Player;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(SpewPlugin::<Enemy, f32>::default())
+        .add_spawners((
+            (Enemy::Zombie, with_context(spawn_enemy)),
+            (Enemy::Boss, with_context(spawn_enemy)),
+        ))
+        .add_systems(Startup, (setup_level, spawn_enemies).chain())
+        .run();
+}
+
+fn setup_level(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Player"),
+        Player,
+        TransformBundle::from_transform(Transform::from_xyz(0.0, 0.0, 0.0)),
+    ));
+    // Zombies crawl out of every grave
+    for x in [-10.0, -5.0, 5.0, 10.0] {
+        commands.spawn((
+            Name::new("Grave"),
+            SpawnPoint::<Enemy>::only(spew::matches!(Enemy::Zombie)),
+            TransformBundle::from_transform(Transform::from_xyz(x, 0.0, 0.0)),
+        ));
+    }
+    // The boss only appears on its throne
+    commands.spawn((
+        Name::new("Throne"),
+        SpawnPoint::only(spew::matches!(Enemy::Boss)),
+        TransformBundle::from_transform(Transform::from_xyz(0.0, 0.0, 20.0)),
+    ));
+}
+
+fn spawn_enemies(
+    players: Query<Entity, With<Player>>,
+    mut spawn_events: EventWriter<SpawnEvent<Enemy, f32>>,
+) {
+    let players: Vec<_> = players.iter().collect();
+    // Spawn points are picked right before spawning, so wait a frame for bevy to propagate their transforms
+    spawn_events.send(
+        SpawnEvent::with_data(Enemy::Zombie, 10.0)
+            .at_spawn_point(SpawnPointSelector::RoundRobin)
+            .delay_frames(1)
+            .repeat(7, Interval::Seconds(Duration::from_secs(1))),
+    );
+    spawn_events.send(
+        SpawnEvent::with_data(Enemy::Zombie, 25.0)
+            .at_spawn_point(SpawnPointSelector::FarthestFrom(players))
+            .delay_seconds(3.0),
+    );
+    spawn_events.send(
+        SpawnEvent::with_data(Enemy::Boss, 500.0)
+            .at_spawn_point(SpawnPointSelector::Random)
+            .delay_seconds(10.0),
+    );
+}
+
+fn spawn_enemy(In(context): In<SpawnContext<Enemy, f32>>, mut commands: Commands) {
+    let transform = context.spawn_point.unwrap_or_default().compute_transform();
+    info!(
+        "Spawning {:?} with {} health at {}",
+        context.object, context.data, transform.translation
+    );
+    commands.spawn((
+        Name::new(format!("{:?}", context.object)),
+        TransformBundle::from_transform(transform),
+    ));
+}
@@ -0,0 +1,38 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+use spew::prelude::*;
+
+#[derive(Debug, Eq, PartialEq, Clone, Deserialize)]
+enum Enemy {
+    Goblin,
+    Dragon,
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        // The data is the x coordinate of the enemy
+        .add_plugins(SpewPlugin::<Enemy, f32>::default().spawn_tables())
+        .add_spawners(((Enemy::Goblin, spawn_goblin), (Enemy::Dragon, spawn_dragon)))
+        .add_systems(Startup, start_wave)
+        .run();
+}
+
+// Edit `assets/waves/first.spawn.ron` to change the wave without recompiling.
+// With bevy's `file_watcher` feature, the wave restarts whenever the file is saved while it is still playing.
+fn start_wave(
+    asset_server: Res<AssetServer>,
+    mut play_events: EventWriter<PlaySpawnTable<Enemy, f32>>,
+) {
+    play_events.send(PlaySpawnTable(asset_server.load("waves/first.spawn.ron")));
+}
+
+fn spawn_goblin(In(x): In<f32>, mut commands: Commands) {
+    info!("Spawning goblin at {x}");
+    commands.spawn((Name::new("Goblin"), Transform::from_xyz(x, 0.0, 0.0)));
+}
+
+fn spawn_dragon(In(x): In<f32>, mut commands: Commands) {
+    info!("Spawning dragon at {x}");
+    commands.spawn((Name::new("Dragon"), Transform::from_xyz(x, 0.0, 0.0)));
+}
@@ -0,0 +1,82 @@
+use bevy::prelude::*;
+use spew::prelude::*;
+use std::time::Duration;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+enum Enemy {
+    Goblin,
+    Troll,
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(SpewPlugin::<Enemy, Transform>::default())
+        .add_spawners(((Enemy::Goblin, spawn_goblin), (Enemy::Troll, spawn_troll)))
+        .add_systems(Startup, start_waves)
+        .add_systems(Update, (report_waves, kill_enemies))
+        .run();
+}
+
+fn start_waves(mut wave_events: EventWriter<StartWave<Enemy, Transform>>) {
+    for wave in 1..=3 {
+        // Each wave has more goblins than the last one, followed by a troll
+        wave_events.send(StartWave(
+            SpawnWave::new()
+                .group(
+                    WaveGroup::new(Enemy::Goblin, wave * 3, |index| {
+                        Transform::from_xyz(index as f32, 0.0, 0.0)
+                    })
+                    .interval(Delay::Seconds(Duration::from_millis(500))),
+                )
+                .group(
+                    WaveGroup::new(Enemy::Troll, 1, |_| Transform::default())
+                        .interval(Delay::Seconds(Duration::from_secs(2))),
+                ),
+        ));
+    }
+}
+
+#[derive(Component)]
+struct Lifetime(Timer);
+
+fn spawn_goblin(In(transform): In<Transform>, mut commands: Commands) {
+    commands.spawn((
+        Name::new("Goblin"),
+        transform,
+        Lifetime(Timer::from_seconds(3.0, TimerMode::Once)),
+    ));
+}
+
+fn spawn_troll(In(transform): In<Transform>, mut commands: Commands) {
+    commands.spawn((
+        Name::new("Troll"),
+        transform,
+        Lifetime(Timer::from_seconds(5.0, TimerMode::Once)),
+    ));
+}
+
+// Stand-in for actual combat
+fn kill_enemies(
+    time: Res<Time>,
+    mut enemies: Query<(Entity, &mut Lifetime)>,
+    mut despawn_events: EventWriter<DespawnEvent<Enemy>>,
+) {
+    for (entity, mut lifetime) in &mut enemies {
+        if lifetime.0.tick(time.delta()).just_finished() {
+            despawn_events.send(DespawnEvent::new(entity));
+        }
+    }
+}
+
+fn report_waves(
+    mut started_events: EventReader<WaveStarted<Enemy, Transform>>,
+    mut completed_events: EventReader<WaveCompleted<Enemy, Transform>>,
+) {
+    for event in started_events.read() {
+        info!("Wave {} started", event.number + 1);
+    }
+    for event in completed_events.read() {
+        info!("Wave {} completed", event.number + 1);
+    }
+}
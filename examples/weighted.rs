@@ -0,0 +1,54 @@
+use bevy::prelude::*;
+use spew::prelude::*;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+enum Enemy {
+    Goblin,
+    Orc,
+    Troll,
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(SpewPlugin::<Enemy, Transform>::default())
+        // Change the seed to get different encounters. The same seed always produces the same encounters.
+        .insert_resource(SpewRng::seeded(1337))
+        .add_spawners((
+            (Enemy::Goblin, spawn_goblin),
+            (Enemy::Orc, spawn_orc),
+            (Enemy::Troll, spawn_troll),
+        ))
+        .add_systems(Startup, spawn_encounter)
+        .run();
+}
+
+fn spawn_encounter(mut weighted_spawns: EventWriter<WeightedSpawn<Enemy, Transform>>) {
+    // Five enemies, each of which is a goblin with a 60% chance, an orc with 30% and a troll with 10%
+    weighted_spawns.send(
+        WeightedSpawn::with_data(
+            [
+                (Enemy::Goblin, 60.0),
+                (Enemy::Orc, 30.0),
+                (Enemy::Troll, 10.0),
+            ],
+            Transform::default(),
+        )
+        .pick(5),
+    );
+}
+
+fn spawn_goblin(In(transform): In<Transform>, mut commands: Commands) {
+    info!("Spawning a goblin");
+    commands.spawn((Name::new("Goblin"), transform));
+}
+
+fn spawn_orc(In(transform): In<Transform>, mut commands: Commands) {
+    info!("Spawning an orc");
+    commands.spawn((Name::new("Orc"), transform));
+}
+
+fn spawn_troll(In(transform): In<Transform>, mut commands: Commands) {
+    info!("Spawning a troll");
+    commands.spawn((Name::new("Troll"), transform));
+}
@@ -0,0 +1,48 @@
+use bevy::prelude::*;
+use spew::prelude::*;
+
+#[derive(Debug, Eq, PartialEq)]
+enum Object {
+    Sword,
+}
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugins(SpewPlugin::<Object, Transform>::default())
+        .add_spawner((Object::Sword, spawn_sword))
+        .add_systems(Startup, spawn_knight)
+        .add_systems(Update, list_equipment.after(SpewSystemSet))
+        .run();
+}
+
+#[derive(Component)]
+struct Knight;
+
+fn spawn_knight(mut commands: Commands) {
+    let knight = commands
+        .spawn((Name::new("Knight"), Knight, SpatialBundle::default()))
+        .id();
+    // The sword will be attached to the knight after it was spawned
+    commands.spew_event(
+        SpawnEvent::with_data(Object::Sword, Transform::from_xyz(0.5, 1.0, 0.0))
+            .with_parent(knight),
+    );
+}
+
+fn spawn_sword(In(transform): In<Transform>, mut commands: Commands) {
+    commands.spawn((Name::new("Sword"), SpatialBundle::from_transform(transform)));
+}
+
+fn list_equipment(
+    knight: Query<&Children, (With<Knight>, Changed<Children>)>,
+    names: Query<&Name>,
+) {
+    for children in &knight {
+        for &child in children {
+            if let Ok(name) = names.get(child) {
+                info!("The knight carries a {name}");
+            }
+        }
+    }
+}
@@ -0,0 +1,187 @@
+#![forbid(missing_docs)]
+//! Derive macros for [spew](https://docs.rs/spew). Enable them with the `derive` feature of spew instead of depending on this crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, Path};
+
+/// Implements `Spewable` for an enum so that all of its spawners can be registered with `app.add_spewable::<T>()`.
+///
+/// Every variant needs a `#[spawner(function)]` attribute naming its spawning function,
+/// or `#[spawner(skip)]` to explicitly register its spawner somewhere else. Forgetting a variant is a compile error,
+/// and so is combining `#[spawner(skip)]` with other spawners.
+/// A variant may have multiple `#[spawner(...)]` attributes to register spawners for different data types.
+/// Only unit variants can be registered, as the generated code has to construct the object.
+///
+/// # Example
+/// ```rust,ignore
+/// use spew::prelude::*;
+/// use bevy::prelude::*;
+///
+/// #[derive(Debug, Eq, PartialEq, Spewable)]
+/// enum Object {
+///     #[spawner(spawn_cube)]
+///     Cube,
+///     #[spawner(spawn_sphere)]
+///     Sphere,
+/// }
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins(DefaultPlugins)
+///         .add_plugins(SpewPlugin::<Object, Transform>::default())
+///         .add_spewable::<Object>()
+///         .run();
+/// }
+///
+/// fn spawn_cube(In(transform): In<Transform>, mut commands: Commands) {
+///     commands.spawn((Name::new("Cube"), transform));
+/// }
+///
+/// fn spawn_sphere(In(transform): In<Transform>, mut commands: Commands) {
+///     commands.spawn((Name::new("Sphere"), transform));
+/// }
+/// ```
+#[proc_macro_derive(Spewable, attributes(spawner))]
+pub fn derive_spewable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_spewable(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand_spewable(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Enum(data) = &input.data else {
+        return Err(Error::new(
+            input.ident.span(),
+            "`Spewable` can only be derived for enums",
+        ));
+    };
+    let name = &input.ident;
+    let mut registrations = Vec::new();
+    let mut errors: Option<Error> = None;
+    let mut push_error = |error: Error| match &mut errors {
+        Some(errors) => errors.combine(error),
+        None => errors = Some(error),
+    };
+
+    for variant in &data.variants {
+        let variant_name = &variant.ident;
+        let spawners = variant
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("spawner"))
+            .map(|attr| attr.parse_args::<Path>())
+            .collect::<syn::Result<Vec<_>>>();
+        let spawners = match spawners {
+            Ok(spawners) => spawners,
+            Err(error) => {
+                push_error(error);
+                continue;
+            }
+        };
+        if spawners.is_empty() {
+            push_error(Error::new(
+                variant.span(),
+                format!(
+                    "`{name}::{variant_name}` has no spawner. \
+                    Add `#[spawner(your_spawn_function)]` or `#[spawner(skip)]` if you register it yourself"
+                ),
+            ));
+            continue;
+        }
+        if let Some(skip) = spawners.iter().find(|spawner| spawner.is_ident("skip")) {
+            if spawners.len() > 1 {
+                push_error(Error::new(
+                    skip.span(),
+                    format!(
+                        "`{name}::{variant_name}` has `#[spawner(skip)]` next to other spawners, which would not be registered. \
+                        Remove either `#[spawner(skip)]` or the other spawners"
+                    ),
+                ));
+            }
+            continue;
+        }
+        if !matches!(variant.fields, Fields::Unit) {
+            push_error(Error::new(
+                variant.fields.span(),
+                format!(
+                    "`{name}::{variant_name}` has fields, so its spawner cannot be registered by `Spewable`. \
+                    Use `#[spawner(skip)]` and register it with `app.add_spawner` instead"
+                ),
+            ));
+            continue;
+        }
+        for spawner in spawners {
+            registrations.push(quote_spanned! {spawner.span()=>
+                ::spew::prelude::SpewApp::add_spawner(app, (#name::#variant_name, #spawner));
+            });
+        }
+    }
+    if let Some(errors) = errors {
+        return Err(errors);
+    }
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::spew::prelude::Spewable for #name #ty_generics #where_clause {
+            fn add_spawners(app: &mut ::spew::__private::App) {
+                #(#registrations)*
+            }
+        }
+    })
+}
+
+/// Implements `SpewVariants` for an enum without fields by listing all of its variants.
+///
+/// # Example
+/// ```rust,ignore
+/// use spew::prelude::*;
+///
+/// #[derive(Debug, Eq, PartialEq, SpewVariants)]
+/// enum Object {
+///     Cube,
+///     Sphere,
+/// }
+/// ```
+#[proc_macro_derive(SpewVariants)]
+pub fn derive_spew_variants(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_spew_variants(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+fn expand_spew_variants(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Enum(data) = &input.data else {
+        return Err(Error::new(
+            input.ident.span(),
+            "`SpewVariants` can only be derived for enums",
+        ));
+    };
+    let name = &input.ident;
+    let mut variants = Vec::new();
+    for variant in &data.variants {
+        let variant_name = &variant.ident;
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(Error::new(
+                variant.fields.span(),
+                format!(
+                    "`{name}::{variant_name}` has fields, so `SpewVariants` cannot list it. Implement the trait yourself instead"
+                ),
+            ));
+        }
+        variants.push(quote! { #name::#variant_name });
+    }
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::spew::prelude::SpewVariants for #name #ty_generics #where_clause {
+            fn variants() -> ::std::vec::Vec<Self> {
+                ::std::vec![#(#variants),*]
+            }
+        }
+    })
+}
@@ -0,0 +1,207 @@
+use crate::events::{ReadySpawnEvent, SpawnHandle};
+use bevy::diagnostic::{Diagnostic, DiagnosticPath, Diagnostics};
+use bevy::prelude::*;
+use bevy::utils::Instant;
+use std::any::type_name;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// How many objects a [`SpewPlugin`](crate::prelude::SpewPlugin) may spawn per run of [`SpewSystemSet`](crate::prelude::SpewSystemSet).
+/// Set it with [`SpewPlugin::spawn_budget`](crate::prelude::SpewPlugin::spawn_budget). The default is [`SpawnBudget::Unlimited`].
+///
+/// Objects that are due but exceed the budget are kept in a backlog and spawned in the following runs, in the order they became due.
+/// At least one object is spawned per run, so the backlog always makes progress.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SpawnBudget {
+    /// Spawn everything that is due right away.
+    #[default]
+    Unlimited,
+    /// Run at most this many spawners per run.
+    Spawns(usize),
+    /// Stop running spawners once this much time was spent on them in the current run.
+    Time(Duration),
+}
+
+impl SpawnBudget {
+    /// Start counting the budget of a single run.
+    fn start(self) -> BudgetTracker {
+        BudgetTracker {
+            budget: self,
+            started: Instant::now(),
+            spawned: 0,
+        }
+    }
+}
+
+/// The part of a [`SpawnBudget`] that was used up in the current run.
+struct BudgetTracker {
+    budget: SpawnBudget,
+    started: Instant,
+    spawned: usize,
+}
+
+impl BudgetTracker {
+    /// Whether another object may be spawned in this run.
+    fn allows_more(&self) -> bool {
+        if self.spawned == 0 {
+            return true;
+        }
+        match self.budget {
+            SpawnBudget::Unlimited => true,
+            SpawnBudget::Spawns(max) => self.spawned < max,
+            SpawnBudget::Time(max) => self.started.elapsed() < max,
+        }
+    }
+}
+
+/// Spawn requests that are due but were held back by the [`SpawnBudget`], in the order they became due.
+#[derive(Resource)]
+pub(crate) struct SpawnBacklog<T, D>
+where
+    T: Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    queue: VecDeque<ReadySpawnEvent<T, D>>,
+    diagnostic: DiagnosticPath,
+}
+
+impl<T, D> Default for SpawnBacklog<T, D>
+where
+    T: Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self {
+            queue: VecDeque::new(),
+            diagnostic: backlog_diagnostic_path::<T, D>(),
+        }
+    }
+}
+
+impl<T, D> SpawnBacklog<T, D>
+where
+    T: Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    /// Whether a spawn with the given handle is waiting in the backlog.
+    pub(crate) fn contains(&self, handle: SpawnHandle) -> bool {
+        self.queue.iter().any(|event| event.handle == handle)
+    }
+
+    /// Drop all spawns with the given handle.
+    pub(crate) fn cancel(&mut self, handle: SpawnHandle) {
+        self.queue.retain(|event| event.handle != handle);
+    }
+}
+
+/// The path of the [`Diagnostic`] that reports the size of the backlog. See [`SpewPlugin::backlog_diagnostic`](crate::prelude::SpewPlugin::backlog_diagnostic).
+pub(crate) fn backlog_diagnostic_path<T, D>() -> DiagnosticPath {
+    DiagnosticPath::new(format!(
+        "spew/{}/{}/backlog",
+        type_name::<T>(),
+        type_name::<D>()
+    ))
+}
+
+/// The diagnostic registered by every [`SpewPlugin`](crate::prelude::SpewPlugin).
+pub(crate) fn backlog_diagnostic<T, D>() -> Diagnostic {
+    Diagnostic::new(backlog_diagnostic_path::<T, D>()).with_suffix(" spawns")
+}
+
+/// Take due spawn requests out of the backlog one by one and hand them to `spawn`, until the backlog is empty or the budget is used up.
+/// New requests sent while spawning, e.g. by spawners using [`SpewCommandsExt`](crate::prelude::SpewCommandsExt), are added to the end of the backlog first.
+pub(crate) fn spend_budget<T, D>(
+    world: &mut World,
+    budget: SpawnBudget,
+    mut spawn: impl FnMut(&mut World, ReadySpawnEvent<T, D>),
+) where
+    T: Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    let mut tracker = budget.start();
+    loop {
+        let ready: Vec<_> = world
+            .resource_mut::<Events<ReadySpawnEvent<T, D>>>()
+            .drain()
+            .collect();
+        let mut backlog = world.resource_mut::<SpawnBacklog<T, D>>();
+        backlog.queue.extend(ready);
+        if !tracker.allows_more() {
+            return;
+        }
+        let Some(event) = backlog.queue.pop_front() else {
+            return;
+        };
+        spawn(world, event);
+        tracker.spawned += 1;
+    }
+}
+
+pub(crate) fn measure_backlog<T, D>(backlog: Res<SpawnBacklog<T, D>>, mut diagnostics: Diagnostics)
+where
+    T: Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    diagnostics.add_measurement(&backlog.diagnostic, || backlog.queue.len() as f64);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::test_support::{self, app, send, Object};
+    use bevy::diagnostic::DiagnosticsStore;
+    use bevy::prelude::*;
+
+    /// Run the app once and return what was spawned in that run and the size of the backlog afterwards.
+    fn update(app: &mut App) -> (Vec<u32>, f64) {
+        let spawned = test_support::update(app);
+        let backlog = SpewPlugin::<Object, u32>::backlog_diagnostic();
+        let backlog = app
+            .world()
+            .resource::<DiagnosticsStore>()
+            .get(&backlog)
+            .and_then(|diagnostic| diagnostic.value())
+            .unwrap_or_default();
+        (spawned, backlog)
+    }
+
+    #[test]
+    fn backlog_is_spawned_in_order() {
+        let mut app = app(SpewPlugin::default().spawn_budget(SpawnBudget::Spawns(2)));
+        for id in 1..=5 {
+            send(&mut app, SpawnEvent::with_data(Object::Marker, id));
+        }
+        send(
+            &mut app,
+            SpawnEvent::with_data(Object::Marker, 6).delay_frames(1),
+        );
+        send(
+            &mut app,
+            SpawnEvent::with_data(Object::Marker, 7).delay_frames(2),
+        );
+        send(
+            &mut app,
+            SpawnEvent::with_data(Object::Marker, 8).delay_frames(4),
+        );
+
+        // Spawns that become due while older ones are still waiting have to wait for them.
+        assert_eq!(update(&mut app), (vec![1, 2], 3.0));
+        assert_eq!(update(&mut app), (vec![3, 4], 2.0));
+        assert_eq!(update(&mut app), (vec![5, 6], 1.0));
+        assert_eq!(update(&mut app), (vec![7], 0.0));
+        assert_eq!(update(&mut app), (vec![8], 0.0));
+        assert_eq!(update(&mut app), (vec![], 0.0));
+    }
+
+    #[test]
+    fn cancel_waiting_spawn() {
+        let mut app = app(SpewPlugin::default().spawn_budget(SpawnBudget::Spawns(1)));
+        send(&mut app, SpawnEvent::with_data(Object::Marker, 1));
+        let handle = send(&mut app, SpawnEvent::with_data(Object::Marker, 2));
+        send(&mut app, SpawnEvent::with_data(Object::Marker, 3));
+        assert_eq!(update(&mut app), (vec![1], 2.0));
+        app.world_mut().send_event(CancelSpawn(handle));
+        assert_eq!(update(&mut app), (vec![3], 0.0));
+        assert_eq!(update(&mut app), (vec![], 0.0));
+    }
+}
@@ -0,0 +1,194 @@
+use crate::error::MissingPluginError;
+use crate::events::{ReadySpawnEvent, SpawnEvent, SpawnHandle};
+use crate::pending::{CancelSpawn, PauseSpawn, ResumeSpawn};
+use crate::plugin::SpewConfig;
+use crate::pool;
+use crate::spawner::spawn_immediately;
+use bevy::core::FrameCount;
+use bevy::ecs::world::Command;
+use bevy::prelude::*;
+use std::fmt::Debug;
+
+/// An extension trait that allows spawning objects through [`Commands`].
+/// In contrast to sending a [`SpawnEvent`] through an [`EventWriter`], the spawner runs as soon as the commands are applied,
+/// which makes it possible to spawn objects from inside other spawners or from observers without waiting for the next run of [`SpewSystemSet`](crate::prelude::SpewSystemSet).
+/// Objects requested from inside a spawner are spawned right after that spawner is done, so they never count as entities spawned by it.
+///
+/// The combination of object and data must have been registered with an own [`SpewPlugin`](crate::prelude::SpewPlugin) beforehand.
+///
+/// # Example
+/// ```rust
+/// use spew::prelude::*;
+/// use bevy::prelude::*;
+///
+/// #[derive(Debug, Eq, PartialEq)]
+/// enum Object {
+///    Tree,
+///    Apple,
+/// }
+///
+/// fn spawn_tree(In(transform): In<Transform>, mut commands: Commands) {
+///     commands.spawn((Name::new("Tree"), transform));
+///     commands.spew(Object::Apple, transform.with_translation(transform.translation + Vec3::Y));
+/// }
+/// ```
+pub trait SpewCommandsExt {
+    /// Spawn an object with the given data as soon as the commands are applied.
+    fn spew<T, D>(&mut self, object: T, data: D) -> SpawnHandle
+    where
+        T: Debug + Eq + Send + Sync + 'static,
+        D: Send + Sync + 'static;
+
+    /// Handle a [`SpawnEvent`] as soon as the commands are applied.
+    /// If the event has a [`Delay`](crate::prelude::Delay), it is handled the same way as if it was sent through an [`EventWriter`].
+    /// Returns the handle of the event, which can be passed to [`SpewCommandsExt::cancel_spawn`].
+    fn spew_event<T, D>(&mut self, event: SpawnEvent<T, D>) -> SpawnHandle
+    where
+        T: Debug + Eq + Send + Sync + 'static,
+        D: Send + Sync + 'static;
+
+    /// Cancel a delayed spawn. This is the same as sending a [`CancelSpawn`] event.
+    fn cancel_spawn(&mut self, handle: SpawnHandle);
+
+    /// Pause a delayed or repeating spawn. This is the same as sending a [`PauseSpawn`] event.
+    fn pause_spawn(&mut self, handle: SpawnHandle);
+
+    /// Resume a paused spawn. This is the same as sending a [`ResumeSpawn`] event.
+    fn resume_spawn(&mut self, handle: SpawnHandle);
+
+    /// Return an entity spawned for a pooled object to its pool so that it can be reused.
+    /// Entities that are not pooled are despawned recursively instead.
+    /// See [`SpewApp::add_pool`](crate::prelude::SpewApp::add_pool) for more information.
+    fn recycle(&mut self, entity: Entity);
+}
+
+impl SpewCommandsExt for Commands<'_, '_> {
+    fn spew<T, D>(&mut self, object: T, data: D) -> SpawnHandle
+    where
+        T: Debug + Eq + Send + Sync + 'static,
+        D: Send + Sync + 'static,
+    {
+        self.spew_event(SpawnEvent::with_data(object, data))
+    }
+
+    fn spew_event<T, D>(&mut self, event: SpawnEvent<T, D>) -> SpawnHandle
+    where
+        T: Debug + Eq + Send + Sync + 'static,
+        D: Send + Sync + 'static,
+    {
+        let handle = event.handle;
+        self.add(SpewCommand(event));
+        handle
+    }
+
+    fn cancel_spawn(&mut self, handle: SpawnHandle) {
+        self.add(move |world: &mut World| {
+            world.send_event(CancelSpawn(handle));
+        });
+    }
+
+    fn pause_spawn(&mut self, handle: SpawnHandle) {
+        self.add(move |world: &mut World| {
+            world.send_event(PauseSpawn(handle));
+        });
+    }
+
+    fn resume_spawn(&mut self, handle: SpawnHandle) {
+        self.add(move |world: &mut World| {
+            world.send_event(ResumeSpawn(handle));
+        });
+    }
+
+    fn recycle(&mut self, entity: Entity) {
+        self.add(move |world: &mut World| pool::recycle(world, entity));
+    }
+}
+
+struct SpewCommand<T, D>(SpawnEvent<T, D>)
+where
+    T: Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static;
+
+impl<T, D> Command for SpewCommand<T, D>
+where
+    T: Debug + Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    fn apply(self, world: &mut World) {
+        let event = self.0;
+        if !world.contains_resource::<SpewConfig<T, D>>() {
+            panic!("{}", MissingPluginError::new::<T, D>(&event.object));
+        }
+        if event.delay.is_over() {
+            if let Some(next_event) = event.next_repetition() {
+                world.send_event(next_event);
+            }
+            let frame = world
+                .get_resource::<FrameCount>()
+                .map_or(0, |frame| frame.0);
+            spawn_immediately(world, ReadySpawnEvent::new(event, frame));
+        } else {
+            world.send_event(event);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use bevy::prelude::*;
+
+    #[derive(Debug, Eq, PartialEq)]
+    enum Building {
+        House,
+    }
+
+    #[derive(Debug, Eq, PartialEq)]
+    enum Resident {
+        Owner,
+    }
+
+    #[derive(Component)]
+    struct House;
+
+    #[derive(Component)]
+    struct Owner;
+
+    fn spawn_house(mut commands: Commands) {
+        commands.spew(Resident::Owner, ());
+        commands.spawn(House);
+    }
+
+    fn spawn_owner(mut commands: Commands) {
+        commands.spawn(Owner);
+    }
+
+    #[test]
+    fn spawn_from_inside_another_spawner() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins((
+                SpewPlugin::<Building>::default(),
+                SpewPlugin::<Resident>::default(),
+            ))
+            .add_spawner((Building::House, spawn_house))
+            .add_spawner((Resident::Owner, spawn_owner));
+        let street = app.world_mut().spawn_empty().id();
+        app.world_mut()
+            .send_event(SpawnEvent::<Building>::new(Building::House).with_parent(street));
+        app.update();
+
+        let world = app.world_mut();
+        let (house_parent, house_resident) = world
+            .query_filtered::<(&Parent, Has<Spewed<Resident>>), (With<House>, With<Spewed<Building>>)>()
+            .single(world);
+        assert_eq!(house_parent.get(), street);
+        assert!(!house_resident);
+        // The owner belongs to its own spawn event, not to the house that requested it
+        let (owner_parent, owner_building) = world
+            .query_filtered::<(Option<&Parent>, Has<Spewed<Building>>), (With<Owner>, With<Spewed<Resident>>)>()
+            .single(world);
+        assert!(owner_parent.is_none());
+        assert!(!owner_building);
+    }
+}
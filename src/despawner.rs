@@ -0,0 +1,421 @@
+use crate::pool::{self, InPool};
+use crate::system::CachedSystem;
+use bevy::prelude::*;
+use bevy::utils::{all_tuples, HashMap};
+use std::any::type_name;
+use std::fmt::{Debug, Formatter};
+use std::mem::{self, Discriminant};
+
+/// An event that despawns an entity that was spawned for an object of type `T`.
+/// If a despawner was registered for the object the entity was spawned for, it runs first.
+/// Afterwards, the entity is despawned recursively, or returned to its pool if it is pooled.
+/// See [`SpewApp::add_despawner`](crate::prelude::SpewApp::add_despawner) for more information.
+///
+/// # Example
+/// ```rust
+/// use spew::prelude::*;
+/// use bevy::prelude::*;
+///
+/// #[derive(Debug, Eq, PartialEq)]
+/// enum Object {
+///    Monster
+/// }
+///
+/// #[derive(Component)]
+/// struct Health(f32);
+///
+/// fn despawn_dead_monsters(
+///     monsters: Query<(Entity, &Health), With<Spewed<Object>>>,
+///     mut despawn_events: EventWriter<DespawnEvent<Object>>,
+/// ) {
+///     for (entity, health) in &monsters {
+///         if health.0 <= 0.0 {
+///             despawn_events.send(DespawnEvent::new(entity));
+///         }
+///     }
+/// }
+/// ```
+#[derive(Event)]
+pub struct DespawnEvent<T>
+where
+    T: Send + Sync + 'static,
+{
+    /// The entity to despawn.
+    pub entity: Entity,
+    _spawner_enum_type: std::marker::PhantomData<T>,
+}
+
+impl<T> DespawnEvent<T>
+where
+    T: Send + Sync + 'static,
+{
+    /// Create a new `DespawnEvent` for the given entity.
+    pub fn new(entity: Entity) -> Self {
+        Self {
+            entity,
+            _spawner_enum_type: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<T> Debug for DespawnEvent<T>
+where
+    T: Send + Sync + 'static,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DespawnEvent")
+            .field("entity", &self.entity)
+            .finish()
+    }
+}
+
+impl<T> Clone for DespawnEvent<T>
+where
+    T: Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for DespawnEvent<T> where T: Send + Sync + 'static {}
+
+impl<T> PartialEq for DespawnEvent<T>
+where
+    T: Send + Sync + 'static,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.entity == other.entity
+    }
+}
+
+impl<T> Eq for DespawnEvent<T> where T: Send + Sync + 'static {}
+
+/// A component that spew inserts on the root entities created by a spawner for an object of type `T`.
+/// It records where the entity came from, so that you can query for entities by the object they were spawned for
+/// and [`DespawnEvent`]s can run the matching despawner.
+///
+/// The object itself is only recorded if the [`SpewPlugin`](crate::prelude::SpewPlugin) was created with
+/// [`SpewPlugin::track_origin`](crate::prelude::SpewPlugin::track_origin), as that requires cloning it.
+/// Use [`Spewed::is`] to check the enum variant without it.
+///
+/// # Example
+/// ```rust
+/// use spew::prelude::*;
+/// use bevy::prelude::*;
+///
+/// #[derive(Debug, Eq, PartialEq)]
+/// enum Object {
+///    Monster,
+///    Coin,
+/// }
+///
+/// fn count_monsters(spewed: Query<&Spewed<Object>>) {
+///     let monsters = spewed.iter().filter(|spewed| spewed.is(&Object::Monster)).count();
+///     info!("There are {monsters} monsters");
+/// }
+/// ```
+#[derive(Component)]
+pub struct Spewed<T>
+where
+    T: Send + Sync + 'static,
+{
+    discriminant: Discriminant<T>,
+    /// The object the entity was spawned for, if [`SpewPlugin::track_origin`](crate::prelude::SpewPlugin::track_origin) is enabled.
+    pub object: Option<T>,
+    /// The [`FrameCount`](bevy::core::FrameCount) of the frame the entity was spawned in.
+    pub frame: u32,
+    /// The correlation id of the [`SpawnEvent`](crate::prelude::SpawnEvent) that requested the entity.
+    pub correlation_id: Option<u64>,
+}
+
+impl<T> Spewed<T>
+where
+    T: Send + Sync + 'static,
+{
+    pub(crate) fn new(
+        object: &T,
+        clone_object: Option<fn(&T) -> T>,
+        frame: u32,
+        correlation_id: Option<u64>,
+    ) -> Self {
+        Self {
+            discriminant: mem::discriminant(object),
+            object: clone_object.map(|clone_object| clone_object(object)),
+            frame,
+            correlation_id,
+        }
+    }
+
+    /// Whether the entity was spawned for the same enum variant as the given object.
+    /// The fields of the variants are not compared.
+    pub fn is(&self, object: &T) -> bool {
+        self.discriminant == mem::discriminant(object)
+    }
+
+    /// The discriminant of the enum variant the entity was spawned for.
+    pub fn discriminant(&self) -> Discriminant<T> {
+        self.discriminant
+    }
+}
+
+impl<T> Debug for Spewed<T>
+where
+    T: Debug + Send + Sync + 'static,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Spewed")
+            .field("discriminant", &self.discriminant)
+            .field("object", &self.object)
+            .field("frame", &self.frame)
+            .field("correlation_id", &self.correlation_id)
+            .finish()
+    }
+}
+
+impl<T> Clone for Spewed<T>
+where
+    T: Clone + Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            discriminant: self.discriminant,
+            object: self.object.clone(),
+            frame: self.frame,
+            correlation_id: self.correlation_id,
+        }
+    }
+}
+
+impl<T> PartialEq for Spewed<T>
+where
+    T: PartialEq + Send + Sync + 'static,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.discriminant == other.discriminant
+            && self.object == other.object
+            && self.frame == other.frame
+            && self.correlation_id == other.correlation_id
+    }
+}
+
+impl<T> Eq for Spewed<T> where T: Eq + Send + Sync + 'static {}
+
+/// Abstraction over a tuple of [`Despawner`]s.
+/// See [`SpewApp::add_despawners`](crate::prelude::SpewApp::add_despawners) for more information.
+pub trait Despawners<Marker>: Send + Sync + 'static {
+    /// Add all despawners to the app. Called internally.
+    fn add_to_app(self, app: &mut App);
+}
+
+/// Abstraction over a tuple of an enum variant and a despawning function.
+/// See [`SpewApp::add_despawner`](crate::prelude::SpewApp::add_despawner) for more information.
+pub trait Despawner<Marker>: Send + Sync + 'static {
+    /// Add the despawner to the app. Called internally.
+    fn add_to_app(self, app: &mut App);
+}
+
+impl<T, F, Marker> Despawner<Marker> for (T, F)
+where
+    T: Debug + Eq + Send + Sync + 'static,
+    F: SystemParamFunction<Marker, In = Entity, Out = ()>,
+    Marker: Send + Sync + 'static,
+{
+    fn add_to_app(self, app: &mut App) {
+        let (object, despawn_function) = self;
+        let mut despawn_function = CachedSystem::new(despawn_function);
+        let despawn = move |world: &mut World, entity: Entity| {
+            despawn_function.run(world, entity);
+        };
+        let Some(mut registry) = app.world_mut().get_resource_mut::<DespawnerRegistry<T>>() else {
+            panic!(
+                "Tried to register a despawner for {object:?}, but no `SpewPlugin` for `{object_type}` was added. \
+                Add it with `app.add_plugins(SpewPlugin::<{object_type}>::default())` before registering despawners.",
+                object_type = type_name::<T>(),
+            );
+        };
+        let discriminant = mem::discriminant(&object);
+        if registry.despawners.contains_key(&discriminant) {
+            warn!(
+                "A despawner for the variant of {object:?} was already registered, so only the first one registered is used."
+            );
+            return;
+        }
+        registry.despawners.insert(discriminant, Box::new(despawn));
+    }
+}
+
+type BoxedDespawnFunction = Box<dyn FnMut(&mut World, Entity) + Send + Sync>;
+
+/// All despawners registered for an object type, keyed by the enum discriminant they handle.
+/// In contrast to spawners, despawners don't depend on the data type, so all [`SpewPlugin`](crate::prelude::SpewPlugin)s for `T` share them.
+#[derive(Resource)]
+pub(crate) struct DespawnerRegistry<T>
+where
+    T: Send + Sync + 'static,
+{
+    despawners: HashMap<Discriminant<T>, BoxedDespawnFunction>,
+}
+
+impl<T> Default for DespawnerRegistry<T>
+where
+    T: Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self {
+            despawners: default(),
+        }
+    }
+}
+
+pub(crate) fn despawn_events<T>(world: &mut World)
+where
+    T: Send + Sync + 'static,
+{
+    // Despawners may request more despawns while running, so keep going until nothing is left.
+    loop {
+        let events: Vec<_> = world
+            .resource_mut::<Events<DespawnEvent<T>>>()
+            .drain()
+            .collect();
+        if events.is_empty() {
+            break;
+        }
+        world.resource_scope(|world, mut registry: Mut<DespawnerRegistry<T>>| {
+            for event in events {
+                let Some(entity) = world.get_entity(event.entity) else {
+                    continue;
+                };
+                if entity.contains::<InPool>() {
+                    continue;
+                }
+                let despawn_function = entity
+                    .get::<Spewed<T>>()
+                    .and_then(|spewed| registry.despawners.get_mut(&spewed.discriminant));
+                if let Some(despawn_function) = despawn_function {
+                    despawn_function(world, event.entity);
+                }
+                pool::recycle(world, event.entity);
+            }
+        });
+    }
+}
+
+macro_rules! impl_despawners_tuples {
+    ($(($param: ident, $despawners: ident)),*) => {
+        impl<$($param, $despawners),*> Despawners<($($param,)*)> for ($($despawners,)*)
+        where
+            $($despawners: Despawner<$param>),*
+        {
+            #[allow(non_snake_case, unused_variables)]
+            fn add_to_app(self, app: &mut App) {
+                let ($($despawners,)*) = self;
+                $($despawners.add_to_app(app);)*
+            }
+        }
+    }
+}
+
+all_tuples!(impl_despawners_tuples, 0, 15, S, D);
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use bevy::prelude::*;
+
+    #[derive(Debug, Eq, PartialEq)]
+    enum Object {
+        Monster,
+        Coin,
+    }
+
+    #[derive(Component)]
+    struct Monster;
+
+    #[derive(Component)]
+    struct Loot(&'static str);
+
+    fn spawn_monster(mut commands: Commands) -> Entity {
+        commands
+            .spawn(Monster)
+            .with_children(|monster| {
+                monster.spawn(Name::new("Sword"));
+            })
+            .id()
+    }
+
+    fn spawn_coin(mut commands: Commands) -> Entity {
+        commands.spawn(Name::new("Coin")).id()
+    }
+
+    fn reset_coin(_: In<(Entity, ())>) {}
+
+    fn drop_gold(
+        In(entity): In<Entity>,
+        monsters: Query<(), With<Monster>>,
+        mut commands: Commands,
+    ) {
+        // The despawner runs while the entity is still around
+        assert!(monsters.contains(entity));
+        commands.spawn(Loot("Gold"));
+    }
+
+    fn drop_silver(_: In<Entity>, mut commands: Commands) {
+        commands.spawn(Loot("Silver"));
+    }
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(SpewPlugin::<Object>::default())
+            .add_spawners(((Object::Monster, spawn_monster), (Object::Coin, spawn_coin)))
+            .add_pool(Object::Coin, reset_coin, PoolConfig::default())
+            .add_despawners(((Object::Monster, drop_gold), (Object::Monster, drop_silver)));
+        app
+    }
+
+    fn spawn(app: &mut App, object: Object) -> Entity {
+        app.world_mut()
+            .send_event(SpawnEvent::<Object>::new(object));
+        app.update();
+        let spawned_events = app.world().resource::<Events<SpawnedEvent<Object>>>();
+        let mut reader = spawned_events.get_reader();
+        reader
+            .read(spawned_events)
+            .last()
+            .map(|spawned| spawned.entities[0])
+            .unwrap()
+    }
+
+    #[test]
+    fn despawn_with_first_despawner() {
+        let mut app = app();
+        let monster = spawn(&mut app, Object::Monster);
+        app.world_mut()
+            .send_event(DespawnEvent::<Object>::new(monster));
+        app.update();
+
+        let world = app.world_mut();
+        assert!(world.get_entity(monster).is_none());
+        assert_eq!(world.query::<&Name>().iter(world).count(), 0);
+        let loot: Vec<_> = world
+            .query::<&Loot>()
+            .iter(world)
+            .map(|loot| loot.0)
+            .collect();
+        assert_eq!(loot, vec!["Gold"]);
+    }
+
+    #[test]
+    fn despawn_returns_pooled_entity() {
+        let mut app = app();
+        let coin = spawn(&mut app, Object::Coin);
+        app.world_mut()
+            .send_event(DespawnEvent::<Object>::new(coin));
+        app.update();
+        assert!(app.world().entity(coin).contains::<InPool>());
+
+        assert_eq!(spawn(&mut app, Object::Coin), coin);
+        assert!(!app.world().entity(coin).contains::<InPool>());
+    }
+}
@@ -0,0 +1,83 @@
+use bevy::prelude::Entity;
+use std::any::type_name;
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+
+/// The error raised when an object is registered or spawned with a combination of object and data that has no [`SpewPlugin`](crate::prelude::SpewPlugin).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MissingPluginError {
+    /// The name of the object type `T`.
+    pub object_type: &'static str,
+    /// The name of the data type `D`.
+    pub data_type: &'static str,
+    /// The debug representation of the object that was used.
+    pub object: String,
+}
+
+impl MissingPluginError {
+    pub(crate) fn new<T: Debug, D>(object: &T) -> Self {
+        Self::described::<T, D>(format!("{object:?}"))
+    }
+
+    /// Create the error for something that is not a single object, like a [`Matcher`](crate::prelude::Matcher).
+    pub(crate) fn described<T, D>(object: String) -> Self {
+        Self {
+            object_type: type_name::<T>(),
+            data_type: type_name::<D>(),
+            object,
+        }
+    }
+}
+
+impl Display for MissingPluginError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Tried to use {object} with data of type `{data}`, but no `SpewPlugin::<{object_type}, {data}>` was added. \
+            Add it with `app.add_plugins(SpewPlugin::<{object_type}, {data}>::default())` before registering spawners or spawning objects.",
+            object = self.object,
+            object_type = self.object_type,
+            data = self.data_type,
+        )
+    }
+}
+
+impl Error for MissingPluginError {}
+
+/// The error reported in a [`SpawnFailed`](crate::prelude::SpawnFailed) event when no spawn point could be picked
+/// for a spawn requested with [`SpawnEvent::at_spawn_point`](crate::prelude::SpawnEvent::at_spawn_point).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpawnPointError {
+    /// No [`SpawnPoint`](crate::prelude::SpawnPoint) accepts the object.
+    NoSpawnPoint {
+        /// The debug representation of the object that was requested.
+        object: String,
+    },
+    /// The target of [`SpawnPointSelector::NearestTo`](crate::prelude::SpawnPointSelector::NearestTo) does not exist or has no `GlobalTransform`.
+    MissingTarget {
+        /// The debug representation of the object that was requested.
+        object: String,
+        /// The entity the spawn point should have been nearest to.
+        target: Entity,
+    },
+}
+
+impl Display for SpawnPointError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SpawnPointError::NoSpawnPoint { object } => {
+                write!(f, "No spawn point is available for {object}")
+            }
+            SpawnPointError::MissingTarget { object, target } => write!(
+                f,
+                "{object} was requested at the spawn point nearest to {target}, but {target} does not exist or has no `GlobalTransform`"
+            ),
+        }
+    }
+}
+
+impl Error for SpawnPointError {}
+
+/// The error returned by a fallible spawner, reported in a [`SpawnFailed`](crate::prelude::SpawnFailed) event.
+/// Any error type can be converted into it, including strings.
+pub type SpawnError = Box<dyn Error + Send + Sync>;
@@ -1,4 +1,12 @@
+use crate::budget::SpawnBacklog;
+use crate::pending::{CancelSpawn, PauseSpawn, PendingSpawns, ResumeSpawn};
+use crate::spawn_point::SpawnPointSelector;
+use bevy::core::FrameCount;
 use bevy::prelude::*;
+use std::error::Error;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 
 mod blanket_impls;
 
@@ -6,6 +14,8 @@ mod blanket_impls;
 /// This is the most common way to interact with the plugin.
 /// `T` is the type of the object to spawn, and `D` is the type of the user-provided data.
 /// Any combination of `T` and `D` used in a `SpawnEvent` must have been registered with an own [`SpewPlugin`](crate::prelude::SpewPlugin) beforehand.
+/// Spew takes the events out of the queue when [`SpewSystemSet`](crate::prelude::SpewSystemSet) runs and keeps delayed ones in [`PendingSpawns`],
+/// so your own [`EventReader`]s see each event exactly once if they run before that set.
 ///
 /// # Example
 /// ```rust
@@ -46,6 +56,18 @@ where
     pub data: D,
     /// The delay to apply.
     pub delay: Delay,
+    /// An optional caller-supplied id that is passed back in the resulting [`SpawnedEvent`].
+    pub correlation_id: Option<u64>,
+    /// The handle that identifies this request while it is delayed.
+    /// Use it to cancel the spawn with [`CancelSpawn`] or to look it up in [`PendingSpawns`].
+    /// Clones of the event get their own handle.
+    pub handle: SpawnHandle,
+    /// Whether to spawn the object again after it was spawned. Set it with [`SpawnEvent::repeat`] or [`SpawnEvent::repeat_forever`].
+    pub repeat: Option<Repeat<T, D>>,
+    /// The entity that all root entities created by the spawner will be attached to as children.
+    pub parent: Option<Entity>,
+    /// How to pick the [`SpawnPoint`](crate::prelude::SpawnPoint) to spawn the object at. Set it with [`SpawnEvent::at_spawn_point`].
+    pub spawn_point: Option<SpawnPointSelector>,
 }
 
 impl Default for Delay {
@@ -81,6 +103,11 @@ where
             object,
             data: default(),
             delay: default(),
+            correlation_id: None,
+            handle: default(),
+            repeat: None,
+            parent: None,
+            spawn_point: None,
         }
     }
 }
@@ -110,6 +137,11 @@ where
             object,
             data,
             delay: default(),
+            correlation_id: None,
+            handle: default(),
+            repeat: None,
+            parent: None,
+            spawn_point: None,
         }
     }
 
@@ -138,6 +170,7 @@ where
 
     /// Delay the spawning of the object by a number of seconds.
     /// A delay of 0.0 means that the object will be spawned in this frame.
+    /// Negative delays count as 0.0, while delays that are too large for a [`Duration`] mean that the object is never spawned.
     ///
     /// # Example
     /// ```rust
@@ -154,10 +187,76 @@ where
     /// assert!(matches!(spawn_event.delay, Delay::Seconds(_)));
     /// ```
     pub fn delay_seconds(mut self, delay: f32) -> Self {
+        let delay = Duration::try_from_secs_f32(delay.max(0.0)).unwrap_or(Duration::MAX);
         self.delay = Delay::Seconds(delay);
         self
     }
 
+    /// Delay the spawning of the object by the given duration.
+    /// A zero duration means that the object will be spawned in this frame.
+    ///
+    /// # Example
+    /// ```rust
+    /// use spew::prelude::*;
+    /// use bevy::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// #[derive(Debug, Eq, PartialEq)]
+    /// enum Object {
+    ///     Cube
+    /// }
+    ///
+    /// let spawn_event: SpawnEvent<Object> = SpawnEvent::new(Object::Cube).delay_duration(Duration::from_millis(1500));
+    /// assert_eq!(spawn_event.delay, Delay::Seconds(Duration::from_millis(1500)));
+    /// ```
+    pub fn delay_duration(mut self, delay: Duration) -> Self {
+        self.delay = Delay::Seconds(delay);
+        self
+    }
+
+    /// Spawn the object on the given frame, as counted by [`FrameCount`].
+    /// If the frame has already passed, the object will be spawned right away.
+    ///
+    /// # Example
+    /// ```rust
+    /// use spew::prelude::*;
+    /// use bevy::prelude::*;
+    ///
+    /// #[derive(Debug, Eq, PartialEq)]
+    /// enum Object {
+    ///     Cube
+    /// }
+    ///
+    /// let spawn_event: SpawnEvent<Object> = SpawnEvent::new(Object::Cube).at_frame(100);
+    /// assert_eq!(spawn_event.delay, Delay::AtFrame(100));
+    /// ```
+    pub fn at_frame(mut self, frame: u32) -> Self {
+        self.delay = Delay::AtFrame(frame);
+        self
+    }
+
+    /// Spawn the object once [`Time::elapsed`] has reached the given duration.
+    /// If that time has already passed, the object will be spawned right away.
+    ///
+    /// # Example
+    /// ```rust
+    /// use spew::prelude::*;
+    /// use bevy::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// #[derive(Debug, Eq, PartialEq)]
+    /// enum Object {
+    ///     Cube
+    /// }
+    ///
+    /// let spawn_event: SpawnEvent<Object> = SpawnEvent::new(Object::Cube).at_elapsed(Duration::from_secs_f32(93.5));
+    /// assert_eq!(spawn_event.delay, Delay::AtElapsed(Duration::from_secs_f32(93.5)));
+    /// ```
+    pub fn at_elapsed(mut self, elapsed: Duration) -> Self {
+        self.delay = Delay::AtElapsed(elapsed);
+        self
+    }
+
     /// Change the provided data. This is useful when using [`SpawnEvent::new`], since it initializes the data with the default value.
     /// # Example
     /// ```rust
@@ -177,52 +276,386 @@ where
         self.data = data;
         self
     }
+
+    /// Attach an id to this event that will be passed back in the [`SpawnedEvent`] sent after the object was spawned.
+    /// Use this to find out which entities were spawned for a specific request.
+    /// # Example
+    /// ```rust
+    /// use spew::prelude::*;
+    /// use bevy::prelude::*;
+    ///
+    /// #[derive(Debug, Eq, PartialEq)]
+    /// enum Object {
+    ///    Cube
+    /// }
+    ///
+    /// let spawn_event: SpawnEvent<Object> = SpawnEvent::new(Object::Cube).correlation_id(42);
+    /// assert_eq!(spawn_event.correlation_id, Some(42));
+    /// ```
+    pub fn correlation_id(mut self, id: u64) -> Self {
+        self.correlation_id = Some(id);
+        self
+    }
+
+    /// Attach all root entities created by the spawner as children of the given entity, like adding a child with [`BuildChildren::add_child`].
+    /// Root entities are the entities that are spawned without a [`Parent`].
+    /// If the parent is despawned before the object is spawned, e.g. because of a [`Delay`], the object is not spawned and a warning is logged.
+    ///
+    /// # Example
+    /// ```rust
+    /// use spew::prelude::*;
+    /// use bevy::prelude::*;
+    ///
+    /// #[derive(Debug, Eq, PartialEq)]
+    /// enum Object {
+    ///     Sword
+    /// }
+    ///
+    /// #[derive(Component)]
+    /// struct Hand;
+    ///
+    /// fn equip_sword(hand: Query<Entity, With<Hand>>, mut spawn_events: EventWriter<SpawnEvent<Object>>) {
+    ///     let hand = hand.single();
+    ///     spawn_events.send(SpawnEvent::new(Object::Sword).with_parent(hand));
+    /// }
+    /// ```
+    pub fn with_parent(mut self, parent: Entity) -> Self {
+        self.parent = Some(parent);
+        self
+    }
+
+    /// Spawn the object at one of the [`SpawnPoint`](crate::prelude::SpawnPoint)s that accept it, picked by the selector.
+    /// The [`GlobalTransform`] of the spawn point is passed to spawners wrapped with [`with_context`](crate::prelude::with_context) in [`SpawnContext::spawn_point`](crate::prelude::SpawnContext::spawn_point).
+    /// The spawn point is picked right before spawning, so delayed and repeated spawns use the spawn points that exist at that time.
+    /// If no spawn point accepts the object or the target of [`SpawnPointSelector::NearestTo`](crate::prelude::SpawnPointSelector::NearestTo) has no [`GlobalTransform`],
+    /// the spawner is not run and a [`SpawnFailed`] event with a [`SpawnPointError`](crate::prelude::SpawnPointError) is sent instead.
+    ///
+    /// Only spawners wrapped with [`with_context`](crate::prelude::with_context) can receive the spawn point.
+    /// Spawners with an `In<D>` parameter, spawners for a [`Matcher`](crate::prelude::Matcher) with an `In<(T, D)>` parameter and the reset functions of pools can't,
+    /// so instead of spawning the object somewhere else, the spawn fails with a [`SpawnFailed`] event.
+    ///
+    /// # Example
+    /// ```rust
+    /// use spew::prelude::*;
+    /// use bevy::prelude::*;
+    ///
+    /// #[derive(Debug, Eq, PartialEq, Clone)]
+    /// enum Enemy {
+    ///     Zombie
+    /// }
+    ///
+    /// #[derive(Component)]
+    /// struct Player;
+    ///
+    /// App::new()
+    ///     .add_plugins(SpewPlugin::<Enemy>::default())
+    ///     .add_spawner((Enemy::Zombie, with_context(spawn_enemy)));
+    ///
+    /// fn spawn_zombie(players: Query<Entity, With<Player>>, mut spawn_events: EventWriter<SpawnEvent<Enemy>>) {
+    ///     let selector = SpawnPointSelector::FarthestFrom(players.iter().collect());
+    ///     spawn_events.send(SpawnEvent::new(Enemy::Zombie).at_spawn_point(selector));
+    /// }
+    ///
+    /// fn spawn_enemy(In(context): In<SpawnContext<Enemy, ()>>, mut commands: Commands) {
+    ///     let transform = context.spawn_point.unwrap_or_default().compute_transform();
+    ///     commands.spawn((Name::new("Zombie"), TransformBundle::from_transform(transform)));
+    /// }
+    /// ```
+    pub fn at_spawn_point(mut self, selector: SpawnPointSelector) -> Self {
+        self.spawn_point = Some(selector);
+        self
+    }
+
+    /// The event for the next spawn of a repeating event, if there is one.
+    pub(crate) fn next_repetition(&self) -> Option<SpawnEvent<T, D>> {
+        let repeat = self.repeat?;
+        let remaining = match repeat.remaining {
+            Some(0) => return None,
+            remaining => remaining.map(|remaining| remaining - 1),
+        };
+        let (object, data) = (repeat.clone)(&self.object, &self.data);
+        Some(SpawnEvent {
+            object,
+            data,
+            delay: repeat.interval.into(),
+            correlation_id: self.correlation_id,
+            handle: self.handle,
+            repeat: Some(Repeat {
+                remaining,
+                ..repeat
+            }),
+            parent: self.parent,
+            spawn_point: self.spawn_point.clone(),
+        })
+    }
+}
+
+impl<T, D> SpawnEvent<T, D>
+where
+    T: Eq + Send + Sync + Clone + 'static,
+    D: Send + Sync + Clone + 'static,
+{
+    /// After spawning the object, spawn it `count` more times with the same data, waiting `interval` between spawns.
+    /// All repetitions share the [`SpawnHandle`] of this event, so they can be paused, resumed and cancelled together.
+    ///
+    /// # Example
+    /// ```rust
+    /// use spew::prelude::*;
+    /// use bevy::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// #[derive(Debug, Eq, PartialEq, Clone)]
+    /// enum Object {
+    ///     Enemy
+    /// }
+    ///
+    /// // Spawns an enemy now and two more in intervals of 3 seconds
+    /// let spawn_event: SpawnEvent<Object> = SpawnEvent::new(Object::Enemy).repeat(2, Interval::Seconds(Duration::from_secs(3)));
+    /// assert_eq!(spawn_event.repeat.unwrap().remaining, Some(2));
+    /// ```
+    pub fn repeat(mut self, count: u32, interval: Interval) -> Self {
+        self.repeat = Some(Repeat::new(Some(count), interval));
+        self
+    }
+
+    /// After spawning the object, keep spawning it with the same data every `interval` until the event is cancelled.
+    /// See [`SpawnEvent::repeat`] for more information.
+    ///
+    /// # Example
+    /// ```rust
+    /// use spew::prelude::*;
+    /// use bevy::prelude::*;
+    ///
+    /// #[derive(Debug, Eq, PartialEq, Clone)]
+    /// enum Object {
+    ///     Enemy
+    /// }
+    ///
+    /// let spawn_event: SpawnEvent<Object> = SpawnEvent::new(Object::Enemy).repeat_forever(Interval::Frames(60));
+    /// assert_eq!(spawn_event.repeat.unwrap().remaining, None);
+    /// ```
+    pub fn repeat_forever(mut self, interval: Interval) -> Self {
+        self.repeat = Some(Repeat::new(None, interval));
+        self
+    }
+}
+
+/// Describes how a [`SpawnEvent`] is repeated. Create it with [`SpawnEvent::repeat`] or [`SpawnEvent::repeat_forever`].
+pub struct Repeat<T, D> {
+    /// How many more times the object will be spawned after the next spawn. `None` means forever.
+    pub remaining: Option<u32>,
+    /// The time between two spawns.
+    pub interval: Interval,
+    clone: fn(&T, &D) -> (T, D),
+}
+
+impl<T, D> Repeat<T, D>
+where
+    T: Clone,
+    D: Clone,
+{
+    fn new(remaining: Option<u32>, interval: Interval) -> Self {
+        Self {
+            remaining,
+            interval,
+            clone: |object, data| (object.clone(), data.clone()),
+        }
+    }
+}
+
+impl<T, D> Clone for Repeat<T, D> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, D> Copy for Repeat<T, D> {}
+
+/// The time between two spawns of a repeating [`SpawnEvent`].
+/// Unlike a [`Delay`], it is always counted from the previous spawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interval {
+    /// Wait for a number of frames between spawns.
+    Frames(usize),
+    /// Wait for some time between spawns.
+    Seconds(Duration),
+}
+
+impl From<Interval> for Delay {
+    fn from(interval: Interval) -> Self {
+        match interval {
+            Interval::Frames(frames) => Delay::Frames(frames),
+            Interval::Seconds(duration) => Delay::Seconds(duration),
+        }
+    }
+}
+
+/// A unique identifier of a [`SpawnEvent`].
+/// Every newly created event gets its own handle, which can be used to cancel the spawn while it is delayed.
+/// Cloning an event also creates a new handle, and handles are ignored when comparing events.
+///
+/// # Example
+/// ```rust
+/// use spew::prelude::*;
+/// use bevy::prelude::*;
+///
+/// #[derive(Debug, Eq, PartialEq)]
+/// enum Object {
+///     Minion
+/// }
+///
+/// #[derive(Resource)]
+/// struct DelayedMinion(SpawnHandle);
+///
+/// fn summon_minion(mut spawn_events: EventWriter<SpawnEvent<Object>>, mut commands: Commands) {
+///     let spawn_event = SpawnEvent::new(Object::Minion).delay_seconds(5.0);
+///     commands.insert_resource(DelayedMinion(spawn_event.handle));
+///     spawn_events.send(spawn_event);
+/// }
+///
+/// fn on_boss_death(minion: Res<DelayedMinion>, mut cancel_events: EventWriter<CancelSpawn>) {
+///     cancel_events.send(CancelSpawn(minion.0));
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct SpawnHandle(u64);
+
+impl Default for SpawnHandle {
+    /// Create a new handle that is different from all handles created before.
+    fn default() -> Self {
+        static NEXT_HANDLE: AtomicU64 = AtomicU64::new(0);
+        Self(NEXT_HANDLE.fetch_add(1, Ordering::Relaxed))
+    }
 }
 
 /// A delay for spawning an object. The default is no delay.
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// Relative delays are converted to an absolute point in time as soon as the [`SpawnEvent`] is received, so they don't drift.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Delay {
     /// Wait for a number of frames before spawning.
     Frames(usize),
-    /// Wait for a number of seconds before spawning the object.
-    Seconds(f32),
+    /// Wait for some time before spawning the object.
+    Seconds(Duration),
+    /// Spawn the object on the given [`FrameCount`]. Requires the [`FrameCountPlugin`](bevy::core::FrameCountPlugin), which is part of the `DefaultPlugins` and `MinimalPlugins`.
+    AtFrame(u32),
+    /// Spawn the object once [`Time::elapsed`] reaches the given duration.
+    AtElapsed(Duration),
+}
+
+impl Delay {
+    /// Whether the object should be spawned right now, without knowing the current frame or time.
+    /// Absolute delays are never considered over here, since they need to be compared to the clock.
+    pub(crate) fn is_over(&self) -> bool {
+        match self {
+            Delay::Frames(delay) => *delay == 0,
+            Delay::Seconds(delay) => delay.is_zero(),
+            Delay::AtFrame(_) | Delay::AtElapsed(_) => false,
+        }
+    }
+
+    /// The relative delay multiplied by `factor`, saturating at the longest representable delay. Absolute delays stay the same.
+    pub(crate) fn times(self, factor: u32) -> Delay {
+        match self {
+            Delay::Frames(delay) => Delay::Frames(delay.saturating_mul(factor as usize)),
+            Delay::Seconds(delay) => Delay::Seconds(delay.saturating_mul(factor)),
+            Delay::AtFrame(_) | Delay::AtElapsed(_) => self,
+        }
+    }
+}
+
+/// An event that spawns many instances of the same object at once, each with its own data.
+/// This is much cheaper than sending a [`SpawnEvent`] per instance when populating a level with hundreds of objects.
+///
+/// If a batch spawner was registered for the object with [`batch`](crate::prelude::batch), it receives all data at once in an `In<Vec<D>>` parameter
+/// and can use [`Commands::spawn_batch`]. Otherwise, the regular spawner runs once per instance.
+/// Batches are spawned in the frame they are sent in, after the regular spawn events.
+///
+/// A batch succeeds or fails as a whole: a single [`SpawnedEvent`] reports all spawned entities,
+/// while a failing spawner despawns everything spawned for the batch so far and sends a single [`SpawnFailed`] event without data that is never retried.
+///
+/// # Example
+/// ```rust
+/// use spew::prelude::*;
+/// use bevy::prelude::*;
+///
+/// #[derive(Debug, Eq, PartialEq)]
+/// enum Object {
+///    Tree
+/// }
+///
+/// fn plant_forest(mut spawn_events: EventWriter<SpawnBatchEvent<Object, Transform>>) {
+///     let positions = (0..500).map(|i| Transform::from_xyz(i as f32, 0.0, 0.0));
+///     spawn_events.send(SpawnBatchEvent::new(Object::Tree, positions));
+/// }
+/// ```
+#[derive(Event)]
+pub struct SpawnBatchEvent<T, D = ()>
+where
+    T: Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    /// The object to spawn.
+    pub object: T,
+    /// The user-provided data of every instance to spawn.
+    pub data: Vec<D>,
+    /// An optional caller-supplied id that is passed back in the resulting [`SpawnedEvent`].
+    pub correlation_id: Option<u64>,
+}
+
+impl<T, D> SpawnBatchEvent<T, D>
+where
+    T: Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    /// Create a new batch that spawns the object once for each item of the data.
+    pub fn new(object: T, data: impl IntoIterator<Item = D>) -> Self {
+        Self {
+            object,
+            data: data.into_iter().collect(),
+            correlation_id: None,
+        }
+    }
+
+    /// Attach an id to this batch that will be passed back in the [`SpawnedEvent`] sent after the objects were spawned.
+    pub fn correlation_id(mut self, id: u64) -> Self {
+        self.correlation_id = Some(id);
+        self
+    }
 }
 
 pub(crate) fn delay_spawn_events<T, D>(
     time: Res<Time>,
-    mut delayed_spawn_events: ResMut<Events<SpawnEvent<T, D>>>,
+    frame_count: Option<Res<FrameCount>>,
+    mut spawn_events: ResMut<Events<SpawnEvent<T, D>>>,
+    mut cancel_events: EventReader<CancelSpawn>,
+    mut pause_events: EventReader<PauseSpawn>,
+    mut resume_events: EventReader<ResumeSpawn>,
+    mut pending_spawns: ResMut<PendingSpawns<T, D>>,
+    mut backlog: ResMut<SpawnBacklog<T, D>>,
+    mut retries: ResMut<SpawnRetries<T, D>>,
     mut spawn_event_writer: EventWriter<ReadySpawnEvent<T, D>>,
 ) where
     T: Eq + Send + Sync + 'static,
     D: Send + Sync + 'static,
 {
-    let mut advanced_events = Vec::new();
-    for event in delayed_spawn_events.drain() {
-        match event.delay {
-            Delay::Frames(delay) => {
-                if delay == 0 {
-                    spawn_event_writer.send(event.into());
-                } else {
-                    advanced_events.push(SpawnEvent {
-                        delay: Delay::Frames(delay - 1),
-                        ..event
-                    });
-                }
-            }
-            Delay::Seconds(delay) => {
-                if delay <= 1e-5 {
-                    spawn_event_writer.send(event.into());
-                } else {
-                    advanced_events.push(SpawnEvent {
-                        delay: Delay::Seconds(delay - time.delta_seconds()),
-                        ..event
-                    });
-                }
-            }
-        }
+    let frame = frame_count.map_or(0, |frame_count| frame_count.0);
+    pending_spawns.advance(frame, time.elapsed());
+    for event in spawn_events.drain() {
+        pending_spawns.push(event);
+    }
+    for CancelSpawn(handle) in cancel_events.read() {
+        pending_spawns.cancel(*handle);
+        backlog.cancel(*handle);
+    }
+    for PauseSpawn(handle) in pause_events.read() {
+        pending_spawns.pause(*handle);
+    }
+    for ResumeSpawn(handle) in resume_events.read() {
+        pending_spawns.resume(*handle);
     }
-    for event in advanced_events {
-        delayed_spawn_events.send(event);
+    spawn_event_writer.send_batch(retries.drain(..));
+    for (event, frame) in pending_spawns.pop_due() {
+        spawn_event_writer.send(ReadySpawnEvent::new(event, frame));
     }
 }
 
@@ -234,17 +667,190 @@ where
 {
     pub(crate) object: T,
     pub(crate) data: D,
+    pub(crate) handle: SpawnHandle,
+    pub(crate) correlation_id: Option<u64>,
+    pub(crate) parent: Option<Entity>,
+    pub(crate) spawn_point: Option<SpawnPointSelector>,
+    /// The [`FrameCount`] of the frame the spawn was requested in.
+    pub(crate) frame: u32,
+    /// How many times the spawner already failed to spawn the object.
+    pub(crate) attempt: u32,
 }
 
-impl<T, D> From<SpawnEvent<T, D>> for ReadySpawnEvent<T, D>
+impl<T, D> ReadySpawnEvent<T, D>
 where
     T: Eq + Send + Sync + 'static,
     D: Send + Sync + 'static,
 {
-    fn from(event: SpawnEvent<T, D>) -> Self {
+    pub(crate) fn new(event: SpawnEvent<T, D>, frame: u32) -> Self {
         Self {
             object: event.object,
             data: event.data,
+            handle: event.handle,
+            correlation_id: event.correlation_id,
+            parent: event.parent,
+            spawn_point: event.spawn_point,
+            frame,
+            attempt: 0,
         }
     }
+
+    /// Take the data out of the event, e.g. to move it into a spawner while keeping the rest of the request around.
+    pub(crate) fn take_data(self) -> (ReadySpawnEvent<T, ()>, D) {
+        let ReadySpawnEvent {
+            object,
+            data,
+            handle,
+            correlation_id,
+            parent,
+            spawn_point,
+            frame,
+            attempt,
+        } = self;
+        let event = ReadySpawnEvent {
+            object,
+            data: (),
+            handle,
+            correlation_id,
+            parent,
+            spawn_point,
+            frame,
+            attempt,
+        };
+        (event, data)
+    }
+
+    /// The same request with different data.
+    pub(crate) fn with_data<E>(self, data: E) -> ReadySpawnEvent<T, E>
+    where
+        E: Send + Sync + 'static,
+    {
+        ReadySpawnEvent {
+            object: self.object,
+            data,
+            handle: self.handle,
+            correlation_id: self.correlation_id,
+            parent: self.parent,
+            spawn_point: self.spawn_point,
+            frame: self.frame,
+            attempt: self.attempt,
+        }
+    }
+}
+
+/// An event that is sent after a spawner handled a [`SpawnEvent`].
+/// It contains the object that was requested, the id passed to [`SpawnEvent::correlation_id`] and the entities returned by the spawner.
+/// Spawners that return `()` will report no entities.
+///
+/// # Example
+/// ```rust
+/// use spew::prelude::*;
+/// use bevy::prelude::*;
+///
+/// #[derive(Debug, Eq, PartialEq)]
+/// enum Object {
+///    Cube
+/// }
+///
+/// #[derive(Component)]
+/// struct Selected;
+///
+/// fn spawn_cube(mut commands: Commands) -> Entity {
+///     commands.spawn(Name::new("Cube")).id()
+/// }
+///
+/// fn select_spawned_cubes(mut spawned_events: EventReader<SpawnedEvent<Object>>, mut commands: Commands) {
+///     for event in spawned_events.read() {
+///         for &entity in &event.entities {
+///             commands.entity(entity).insert(Selected);
+///         }
+///     }
+/// }
+/// ```
+#[derive(Event)]
+pub struct SpawnedEvent<T, D = ()>
+where
+    T: Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    /// The object that was spawned.
+    pub object: T,
+    /// The id that was passed to [`SpawnEvent::correlation_id`], if any.
+    pub correlation_id: Option<u64>,
+    /// The entities returned by the spawner.
+    pub entities: Vec<Entity>,
+    pub(crate) _data_type: std::marker::PhantomData<D>,
+}
+
+/// An event that is sent when a fallible spawner returned an error instead of spawning the object.
+/// See [`SpawnerOutput`](crate::prelude::SpawnerOutput) for how to write fallible spawners.
+///
+/// # Example
+/// ```rust
+/// use spew::prelude::*;
+/// use bevy::prelude::*;
+///
+/// #[derive(Debug, Eq, PartialEq)]
+/// enum Object {
+///    Monster
+/// }
+///
+/// fn spawn_monster(mut commands: Commands, spawn_points: Query<&Transform>) -> Result<Entity, &'static str> {
+///     let transform = spawn_points.iter().next().ok_or("No spawn point left")?;
+///     Ok(commands.spawn((Name::new("Monster"), *transform)).id())
+/// }
+///
+/// fn report_failed_monsters(mut failed_events: EventReader<SpawnFailed<Object>>) {
+///     for event in failed_events.read() {
+///         warn!("Failed to spawn {:?}: {}", event.object, event.error);
+///     }
+/// }
+/// ```
+#[derive(Event)]
+pub struct SpawnFailed<T, D = ()>
+where
+    T: Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    /// The object that could not be spawned.
+    pub object: T,
+    /// The data that was passed to the spawner.
+    /// Only available if failed spawns are handled with [`SpewPlugin::failed_spawns`](crate::prelude::SpewPlugin::failed_spawns), as that requires cloning it.
+    pub data: Option<D>,
+    /// The error returned by the spawner, shared between clones of the event.
+    pub error: Arc<dyn Error + Send + Sync>,
+    /// The id that was passed to [`SpawnEvent::correlation_id`], if any.
+    pub correlation_id: Option<u64>,
+    /// How many times spawning the object has failed so far, starting at 1.
+    pub attempt: u32,
+    /// Whether the spawn will be tried again in the next run of [`SpewSystemSet`](crate::prelude::SpewSystemSet).
+    pub retrying: bool,
+}
+
+/// Failed spawns that are retried in the next run of [`SpewSystemSet`](crate::prelude::SpewSystemSet).
+#[derive(Resource, Deref, DerefMut)]
+pub(crate) struct SpawnRetries<T, D>(Vec<ReadySpawnEvent<T, D>>)
+where
+    T: Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static;
+
+impl<T, D> Default for SpawnRetries<T, D>
+where
+    T: Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self(Vec::new())
+    }
+}
+
+impl<T, D> SpawnRetries<T, D>
+where
+    T: Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    /// Whether the spawn with the given handle failed and is waiting to be retried.
+    pub(crate) fn contains(&self, handle: SpawnHandle) -> bool {
+        self.0.iter().any(|event| event.handle == handle)
+    }
 }
@@ -1,4 +1,4 @@
-use crate::events::SpawnEvent;
+use crate::events::{Repeat, SpawnEvent, SpawnFailed, SpawnHandle, SpawnedEvent};
 use std::fmt::{Debug, Formatter};
 
 impl<T, D> Clone for SpawnEvent<T, D>
@@ -6,11 +6,17 @@ where
     T: Eq + Send + Sync + Clone + 'static,
     D: Send + Sync + Clone + 'static,
 {
+    /// Clone the event with a new [`SpawnHandle`], so that the clone can be cancelled independently of the original.
     fn clone(&self) -> Self {
         Self {
             object: self.object.clone(),
             data: self.data.clone(),
             delay: self.delay,
+            correlation_id: self.correlation_id,
+            handle: SpawnHandle::default(),
+            repeat: self.repeat,
+            parent: self.parent,
+            spawn_point: self.spawn_point.clone(),
         }
     }
 }
@@ -21,21 +27,33 @@ where
     D: Send + Sync + Debug + 'static,
 {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("ReadySpawnEvent")
+        f.debug_struct("SpawnEvent")
             .field("object", &self.object)
             .field("data", &self.data)
             .field("delay", &self.delay)
+            .field("correlation_id", &self.correlation_id)
+            .field("handle", &self.handle)
+            .field("repeat", &self.repeat)
+            .field("parent", &self.parent)
+            .field("spawn_point", &self.spawn_point)
             .finish()
     }
 }
 
+/// Compares everything except the [`SpawnHandle`], which is different for every event.
 impl<T, D> PartialEq for SpawnEvent<T, D>
 where
     T: Eq + Send + Sync + PartialEq + 'static,
     D: Send + Sync + PartialEq + 'static,
 {
     fn eq(&self, other: &Self) -> bool {
-        self.object == other.object && self.data == other.data && self.delay == other.delay
+        self.object == other.object
+            && self.data == other.data
+            && self.delay == other.delay
+            && self.correlation_id == other.correlation_id
+            && self.repeat == other.repeat
+            && self.parent == other.parent
+            && self.spawn_point == other.spawn_point
     }
 }
 
@@ -49,6 +67,89 @@ where
             object: Default::default(),
             data: Default::default(),
             delay: Default::default(),
+            correlation_id: Default::default(),
+            handle: Default::default(),
+            repeat: Default::default(),
+            parent: Default::default(),
+            spawn_point: Default::default(),
         }
     }
 }
+
+impl<T, D> Clone for SpawnedEvent<T, D>
+where
+    T: Eq + Send + Sync + Clone + 'static,
+    D: Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            object: self.object.clone(),
+            correlation_id: self.correlation_id,
+            entities: self.entities.clone(),
+            _data_type: self._data_type,
+        }
+    }
+}
+
+impl<T, D> Debug for SpawnedEvent<T, D>
+where
+    T: Eq + Send + Sync + Debug + 'static,
+    D: Send + Sync + 'static,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SpawnedEvent")
+            .field("object", &self.object)
+            .field("correlation_id", &self.correlation_id)
+            .field("entities", &self.entities)
+            .finish()
+    }
+}
+
+impl<T, D> Clone for SpawnFailed<T, D>
+where
+    T: Eq + Send + Sync + Clone + 'static,
+    D: Send + Sync + Clone + 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            object: self.object.clone(),
+            data: self.data.clone(),
+            error: self.error.clone(),
+            correlation_id: self.correlation_id,
+            attempt: self.attempt,
+            retrying: self.retrying,
+        }
+    }
+}
+
+impl<T, D> Debug for SpawnFailed<T, D>
+where
+    T: Eq + Send + Sync + Debug + 'static,
+    D: Send + Sync + Debug + 'static,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SpawnFailed")
+            .field("object", &self.object)
+            .field("data", &self.data)
+            .field("error", &self.error)
+            .field("correlation_id", &self.correlation_id)
+            .field("attempt", &self.attempt)
+            .field("retrying", &self.retrying)
+            .finish()
+    }
+}
+
+impl<T, D> Debug for Repeat<T, D> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Repeat")
+            .field("remaining", &self.remaining)
+            .field("interval", &self.interval)
+            .finish()
+    }
+}
+
+impl<T, D> PartialEq for Repeat<T, D> {
+    fn eq(&self, other: &Self) -> bool {
+        self.remaining == other.remaining && self.interval == other.interval
+    }
+}
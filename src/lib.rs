@@ -3,14 +3,83 @@
 #![forbid(missing_docs)]
 #![doc = include_str!("../readme.md")]
 
+mod budget;
+mod commands;
+mod despawner;
+mod error;
 mod events;
+mod pending;
 mod plugin;
+mod pool;
+mod spawn_point;
+#[cfg(feature = "assets")]
+mod spawn_table;
 mod spawner;
+mod spewable;
+mod system;
+#[cfg(test)]
+mod test_support;
+mod tracking;
+mod wave;
+mod weighted;
+
+/// Create a [`Matcher`](prelude::Matcher) that selects all objects matching a pattern, like [`std::matches!`].
+/// See [`Matcher`](prelude::Matcher) for more information.
+///
+/// # Example
+/// ```rust
+/// use spew::prelude::*;
+///
+/// #[derive(Debug, Eq, PartialEq)]
+/// enum Enemy {
+///    Goblin { level: u8 },
+/// }
+///
+/// let veterans = spew::matches!(Enemy::Goblin { level } if *level > 10);
+/// assert!(veterans.matches(&Enemy::Goblin { level: 11 }));
+/// assert!(!veterans.matches(&Enemy::Goblin { level: 1 }));
+/// ```
+#[macro_export]
+macro_rules! matches {
+    ($pattern:pat $(if $guard:expr)? $(,)?) => {
+        $crate::prelude::Matcher::new(
+            ::core::stringify!($pattern $(if $guard)?),
+            |object| ::core::matches!(object, $pattern $(if $guard)?),
+        )
+    };
+}
 
 /// Everything you need to get started
 pub mod prelude {
     pub use crate::{
-        events::{Delay, SpawnEvent},
-        plugin::{SpewApp, SpewPlugin, SpewSystemSet},
+        budget::SpawnBudget,
+        commands::SpewCommandsExt,
+        despawner::{DespawnEvent, Spewed},
+        error::{MissingPluginError, SpawnError, SpawnPointError},
+        events::{
+            Delay, Interval, Repeat, SpawnBatchEvent, SpawnEvent, SpawnFailed, SpawnHandle,
+            SpawnedEvent,
+        },
+        pending::{CancelSpawn, PauseSpawn, PendingSpawn, PendingSpawns, ResumeSpawn},
+        plugin::{
+            RetryPolicy, SpewApp, SpewPlugin, SpewSystemSet, UnhandledSpawnPolicy, ValidationPolicy,
+        },
+        pool::{InPool, PoolConfig, PoolOverflow},
+        spawn_point::{SpawnPoint, SpawnPointSelector},
+        spawner::{batch, with_context, Matcher, SpawnContext, SpawnerOutput},
+        spewable::{SpewVariants, Spewable},
+        wave::{
+            SpawnWave, StartWave, WaveCompleted, WaveCompletion, WaveGroup, WaveStarted, WaveState,
+        },
+        weighted::{SpewRng, WeightedSpawn},
     };
+
+    #[cfg(feature = "assets")]
+    pub use crate::spawn_table::{PlaySpawnTable, SpawnTable, SpawnTableEntry};
+}
+
+/// Items used by the code generated by the derive macros. Not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use bevy::app::App;
 }
@@ -0,0 +1,494 @@
+use crate::events::{Delay, SpawnEvent, SpawnHandle};
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BinaryHeap};
+use std::time::Duration;
+
+/// An event that cancels a delayed spawn before it happens.
+/// Cancelling a spawn that already happened or was never requested does nothing.
+/// See [`SpawnHandle`] for an example.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CancelSpawn(pub SpawnHandle);
+
+/// An event that pauses a delayed or repeating spawn.
+/// Spawns that become due while paused are held back until a [`ResumeSpawn`] event is sent for the same handle.
+/// Pausing a spawn that already happened or was never requested does nothing.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PauseSpawn(pub SpawnHandle);
+
+/// An event that resumes a spawn paused by [`PauseSpawn`].
+/// Spawns that were held back happen right away and repeating spawns continue their interval from there.
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResumeSpawn(pub SpawnHandle);
+
+/// A resource containing all [`SpawnEvent`]s of type `T` with data `D` that are waiting for their [`Delay`] to pass.
+/// Each request is stored once and handed to its spawner exactly when it is due.
+///
+/// # Example
+/// ```rust
+/// use spew::prelude::*;
+/// use bevy::prelude::*;
+///
+/// #[derive(Debug, Eq, PartialEq)]
+/// enum Object {
+///     Minion
+/// }
+///
+/// fn list_pending_minions(pending_spawns: Res<PendingSpawns<Object>>) {
+///     for pending_spawn in pending_spawns.iter() {
+///         info!("{:?} will spawn after {:?}", pending_spawn.object, pending_spawn.remaining);
+///     }
+/// }
+/// ```
+#[derive(Resource)]
+pub struct PendingSpawns<T, D = ()>
+where
+    T: Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    /// The pending spawns in the order they were requested, keyed by their insertion index.
+    entries: BTreeMap<u64, PendingEntry<T, D>>,
+    /// Insertion indices of spawns delayed by frames, ordered by the tick they are due.
+    tick_queue: BinaryHeap<Reverse<(u64, u64)>>,
+    /// Insertion indices of spawns waiting for a [`FrameCount`](bevy::core::FrameCount), ordered by that frame.
+    frame_queue: BinaryHeap<Reverse<(u32, u64)>>,
+    /// Insertion indices of spawns delayed by time, ordered by the elapsed time they are due.
+    time_queue: BinaryHeap<Reverse<(Duration, u64)>>,
+    /// Handles of paused spawns.
+    paused: HashSet<SpawnHandle>,
+    /// Insertion indices of spawns that became due while paused.
+    held: Vec<u64>,
+    next_index: u64,
+    tick: u64,
+    frame: u32,
+    elapsed: Duration,
+}
+
+struct PendingEntry<T, D>
+where
+    T: Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    event: SpawnEvent<T, D>,
+    due: Due,
+    /// The frame the spawn was requested in. Repetitions keep the frame of the original request.
+    requested_frame: u32,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Due {
+    Tick(u64),
+    Frame(u32),
+    Elapsed(Duration),
+}
+
+impl<T, D> Default for PendingSpawns<T, D>
+where
+    T: Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self {
+            entries: default(),
+            tick_queue: default(),
+            frame_queue: default(),
+            time_queue: default(),
+            paused: default(),
+            held: default(),
+            next_index: 0,
+            tick: 0,
+            frame: 0,
+            elapsed: Duration::ZERO,
+        }
+    }
+}
+
+impl<T, D> PendingSpawns<T, D>
+where
+    T: Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    /// Iterate over all pending spawns in the order they were requested.
+    pub fn iter(&self) -> impl Iterator<Item = PendingSpawn<'_, T, D>> {
+        self.entries.values().map(|entry| self.view(entry))
+    }
+
+    /// Get the pending spawn with the given handle, if it has not happened yet.
+    pub fn get(&self, handle: SpawnHandle) -> Option<PendingSpawn<'_, T, D>> {
+        self.iter()
+            .find(|pending_spawn| pending_spawn.handle == handle)
+    }
+
+    /// Whether a spawn with the given handle is still pending.
+    pub fn contains(&self, handle: SpawnHandle) -> bool {
+        self.get(handle).is_some()
+    }
+
+    /// The number of pending spawns.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether there are no pending spawns.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Cancel the pending spawn with the given handle.
+    /// Returns whether a spawn was cancelled.
+    pub fn cancel(&mut self, handle: SpawnHandle) -> bool {
+        let len = self.entries.len();
+        // The queues are cleaned up lazily when the cancelled entries would have been due.
+        self.entries.retain(|_, entry| entry.event.handle != handle);
+        self.paused.remove(&handle);
+        self.entries.len() != len
+    }
+
+    /// Pause the pending spawn with the given handle. See [`PauseSpawn`] for more information.
+    /// Returns whether a spawn was paused, which is not the case if it is not pending or already paused.
+    pub fn pause(&mut self, handle: SpawnHandle) -> bool {
+        self.entries
+            .values()
+            .any(|entry| entry.event.handle == handle)
+            && self.paused.insert(handle)
+    }
+
+    /// Resume the pending spawn with the given handle. See [`ResumeSpawn`] for more information.
+    /// Returns whether the spawn was paused.
+    pub fn resume(&mut self, handle: SpawnHandle) -> bool {
+        if !self.paused.remove(&handle) {
+            return false;
+        }
+        let (resumed, held): (Vec<_>, Vec<_>) = self.held.drain(..).partition(|index| {
+            self.entries
+                .get(index)
+                .is_some_and(|entry| entry.event.handle == handle)
+        });
+        self.held = held;
+        for index in resumed {
+            if let Some(entry) = self.entries.get_mut(&index) {
+                entry.due = Due::Tick(self.tick);
+                self.tick_queue.push(Reverse((self.tick, index)));
+            }
+        }
+        true
+    }
+
+    /// Whether the spawn with the given handle is paused.
+    pub fn is_paused(&self, handle: SpawnHandle) -> bool {
+        self.paused.contains(&handle)
+    }
+
+    /// Advance the clock by one run of the schedule the spew systems are in.
+    pub(crate) fn advance(&mut self, frame: u32, elapsed: Duration) {
+        self.tick += 1;
+        self.frame = frame;
+        self.elapsed = elapsed;
+    }
+
+    pub(crate) fn push(&mut self, event: SpawnEvent<T, D>) {
+        let due = self.due(event.delay);
+        self.insert(event, due, self.frame);
+    }
+
+    /// When a spawn with the given delay is due if it was requested right now.
+    fn due(&self, delay: Delay) -> Due {
+        match delay {
+            Delay::Frames(frames) => Due::Tick(self.tick.saturating_add(frames as u64)),
+            Delay::Seconds(duration) => Due::Elapsed(self.elapsed.saturating_add(duration)),
+            Delay::AtFrame(frame) => Due::Frame(frame),
+            Delay::AtElapsed(elapsed) => Due::Elapsed(elapsed),
+        }
+    }
+
+    fn insert(&mut self, event: SpawnEvent<T, D>, due: Due, requested_frame: u32) {
+        let index = self.next_index;
+        self.next_index += 1;
+        match due {
+            Due::Tick(tick) => self.tick_queue.push(Reverse((tick, index))),
+            Due::Frame(frame) => self.frame_queue.push(Reverse((frame, index))),
+            Due::Elapsed(elapsed) => self.time_queue.push(Reverse((elapsed, index))),
+        }
+        self.entries.insert(
+            index,
+            PendingEntry {
+                event,
+                due,
+                requested_frame,
+            },
+        );
+    }
+
+    /// Remove all spawns that are due and return them in the order they were requested, together with the frame they were requested in.
+    /// Repeating spawns are scheduled again, counting their interval from when they were due so they don't drift.
+    pub(crate) fn pop_due(&mut self) -> Vec<(SpawnEvent<T, D>, u32)> {
+        let mut due_indices = Vec::new();
+        while let Some(&Reverse((tick, index))) = self.tick_queue.peek() {
+            if tick > self.tick {
+                break;
+            }
+            self.tick_queue.pop();
+            due_indices.push(index);
+        }
+        while let Some(&Reverse((frame, index))) = self.frame_queue.peek() {
+            if frame > self.frame {
+                break;
+            }
+            self.frame_queue.pop();
+            due_indices.push(index);
+        }
+        while let Some(&Reverse((elapsed, index))) = self.time_queue.peek() {
+            if elapsed > self.elapsed {
+                break;
+            }
+            self.time_queue.pop();
+            due_indices.push(index);
+        }
+        due_indices.sort_unstable();
+
+        let mut events = Vec::with_capacity(due_indices.len());
+        for index in due_indices {
+            let Some(entry) = self.entries.get(&index) else {
+                // The spawn was cancelled
+                continue;
+            };
+            if self.paused.contains(&entry.event.handle) {
+                self.held.push(index);
+                continue;
+            }
+            let PendingEntry {
+                event,
+                due,
+                requested_frame,
+            } = self.entries.remove(&index).unwrap();
+            if let Some(next_event) = event.next_repetition() {
+                let next_due = match (due, next_event.delay) {
+                    (Due::Tick(tick), Delay::Frames(frames)) => {
+                        Due::Tick(tick.saturating_add(frames as u64))
+                    }
+                    (Due::Elapsed(elapsed), Delay::Seconds(duration)) => {
+                        Due::Elapsed(elapsed.saturating_add(duration))
+                    }
+                    _ => self.due(next_event.delay),
+                };
+                self.insert(next_event, next_due, requested_frame);
+            }
+            events.push((event, requested_frame));
+        }
+        events
+    }
+
+    fn view<'a>(&self, entry: &'a PendingEntry<T, D>) -> PendingSpawn<'a, T, D> {
+        let remaining = match entry.due {
+            Due::Tick(tick) => Delay::Frames(tick.saturating_sub(self.tick) as usize),
+            Due::Frame(frame) => Delay::AtFrame(frame),
+            Due::Elapsed(elapsed) => match entry.event.delay {
+                Delay::AtElapsed(_) => Delay::AtElapsed(elapsed),
+                _ => Delay::Seconds(elapsed.saturating_sub(self.elapsed)),
+            },
+        };
+        PendingSpawn {
+            handle: entry.event.handle,
+            object: &entry.event.object,
+            data: &entry.event.data,
+            remaining,
+            paused: self.paused.contains(&entry.event.handle),
+        }
+    }
+}
+
+/// A view into a spawn that is waiting for its [`Delay`] to pass.
+#[derive(Debug)]
+pub struct PendingSpawn<'a, T, D> {
+    /// The handle of the [`SpawnEvent`] that requested the spawn.
+    pub handle: SpawnHandle,
+    /// The object that will be spawned.
+    pub object: &'a T,
+    /// The data that will be passed to the spawner.
+    pub data: &'a D,
+    /// The delay that is left before the object is spawned.
+    /// Absolute delays like [`Delay::AtFrame`] are reported as they were requested.
+    pub remaining: Delay,
+    /// Whether the spawn is paused.
+    pub paused: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::test_support::{app, record, send, update, Object, Spawned};
+    use bevy::prelude::*;
+    use std::time::Duration;
+
+    #[test]
+    fn due_spawns_keep_request_order() {
+        let mut app = app(SpewPlugin::default());
+        send(
+            &mut app,
+            SpawnEvent::with_data(Object::Marker, 1).delay_frames(2),
+        );
+        send(
+            &mut app,
+            SpawnEvent::with_data(Object::Marker, 2).delay_duration(Duration::from_millis(150)),
+        );
+        send(
+            &mut app,
+            SpawnEvent::with_data(Object::Marker, 3).at_frame(2),
+        );
+        send(&mut app, SpawnEvent::with_data(Object::Marker, 4));
+        send(
+            &mut app,
+            SpawnEvent::with_data(Object::Marker, 5).at_elapsed(Duration::from_millis(200)),
+        );
+        assert_eq!(update(&mut app), vec![4]);
+        assert_eq!(
+            app.world().resource::<PendingSpawns<Object, u32>>().len(),
+            4
+        );
+        assert_eq!(update(&mut app), Vec::<u32>::new());
+        // Spawns from all kinds of delays that are due in the same run are spawned in the order they were requested
+        assert_eq!(update(&mut app), vec![1, 2, 3, 5]);
+        assert!(app
+            .world()
+            .resource::<PendingSpawns<Object, u32>>()
+            .is_empty());
+    }
+
+    #[test]
+    fn cancel_pause_and_resume() {
+        let mut app = app(SpewPlugin::default());
+        let cancelled = send(
+            &mut app,
+            SpawnEvent::with_data(Object::Marker, 1).delay_frames(1),
+        );
+        let paused = send(
+            &mut app,
+            SpawnEvent::with_data(Object::Marker, 2).delay_frames(1),
+        );
+        send(
+            &mut app,
+            SpawnEvent::with_data(Object::Marker, 3).delay_frames(3),
+        );
+        assert_eq!(update(&mut app), Vec::<u32>::new());
+
+        app.world_mut().send_event(CancelSpawn(cancelled));
+        app.world_mut().send_event(PauseSpawn(paused));
+        assert_eq!(update(&mut app), Vec::<u32>::new());
+        let pending_spawns = app.world().resource::<PendingSpawns<Object, u32>>();
+        assert!(!pending_spawns.contains(cancelled));
+        assert!(pending_spawns.contains(paused));
+        assert!(pending_spawns.is_paused(paused));
+
+        // The paused spawn is held back while others keep going
+        assert_eq!(update(&mut app), Vec::<u32>::new());
+        assert_eq!(update(&mut app), vec![3]);
+        app.world_mut().send_event(ResumeSpawn(paused));
+        assert_eq!(update(&mut app), vec![2]);
+        assert!(app
+            .world()
+            .resource::<PendingSpawns<Object, u32>>()
+            .is_empty());
+    }
+
+    #[test]
+    fn repeats_without_drift() {
+        let mut app = app(SpewPlugin::default());
+        send(
+            &mut app,
+            SpawnEvent::with_data(Object::Marker, 1).repeat(2, Interval::Frames(2)),
+        );
+        // Due at 0ms, 150ms and 300ms, while the runs happen every 100ms.
+        // Counting the interval from the run the spawn happened in would move the last spawn to 350ms.
+        send(
+            &mut app,
+            SpawnEvent::with_data(Object::Marker, 2)
+                .repeat(2, Interval::Seconds(Duration::from_millis(150))),
+        );
+        assert_eq!(update(&mut app), vec![1, 2]);
+        assert_eq!(update(&mut app), Vec::<u32>::new());
+        assert_eq!(update(&mut app), vec![1, 2]);
+        assert_eq!(update(&mut app), vec![2]);
+        assert_eq!(update(&mut app), vec![1]);
+        assert_eq!(update(&mut app), Vec::<u32>::new());
+        assert!(app
+            .world()
+            .resource::<PendingSpawns<Object, u32>>()
+            .is_empty());
+    }
+
+    #[test]
+    fn pause_repeating_spawn() {
+        let mut app = app(SpewPlugin::default());
+        let handle = send(
+            &mut app,
+            SpawnEvent::with_data(Object::Marker, 7).repeat_forever(Interval::Frames(1)),
+        );
+        assert_eq!(update(&mut app), vec![7]);
+        assert_eq!(update(&mut app), vec![7]);
+        app.world_mut().send_event(PauseSpawn(handle));
+        assert_eq!(update(&mut app), Vec::<u32>::new());
+        assert_eq!(update(&mut app), Vec::<u32>::new());
+        // The interval continues from the run the spawn was resumed in
+        app.world_mut().send_event(ResumeSpawn(handle));
+        assert_eq!(update(&mut app), vec![7]);
+        assert_eq!(update(&mut app), vec![7]);
+        app.world_mut().send_event(CancelSpawn(handle));
+        assert_eq!(update(&mut app), Vec::<u32>::new());
+        assert!(app
+            .world()
+            .resource::<PendingSpawns<Object, u32>>()
+            .is_empty());
+    }
+
+    #[test]
+    fn pause_only_pending_spawns() {
+        let mut app = app(SpewPlugin::default());
+        let spawned = send(&mut app, SpawnEvent::with_data(Object::Marker, 1));
+        let delayed = send(
+            &mut app,
+            SpawnEvent::with_data(Object::Marker, 2).delay_frames(2),
+        );
+        assert_eq!(update(&mut app), vec![1]);
+        let mut pending_spawns = app.world_mut().resource_mut::<PendingSpawns<Object, u32>>();
+        assert!(!pending_spawns.pause(spawned));
+        assert!(!pending_spawns.is_paused(spawned));
+        assert!(pending_spawns.pause(delayed));
+        assert!(!pending_spawns.pause(delayed));
+        assert!(pending_spawns.is_paused(delayed));
+    }
+
+    #[test]
+    fn endless_delay() {
+        let mut app = app(SpewPlugin::default());
+        send(
+            &mut app,
+            SpawnEvent::with_data(Object::Marker, 1).delay_seconds(f32::INFINITY),
+        );
+        send(
+            &mut app,
+            SpawnEvent::with_data(Object::Marker, 2).delay_seconds(-1.0),
+        );
+        assert_eq!(update(&mut app), vec![2]);
+        assert_eq!(update(&mut app), Vec::<u32>::new());
+        assert_eq!(
+            app.world().resource::<PendingSpawns<Object, u32>>().len(),
+            1
+        );
+    }
+
+    #[test]
+    fn without_frame_count() {
+        let mut app = App::new();
+        app.add_plugins(bevy::time::TimePlugin)
+            .init_resource::<Spawned>()
+            .add_plugins(SpewPlugin::<Object, u32>::default())
+            .add_spawner((Object::Marker, record));
+        send(
+            &mut app,
+            SpawnEvent::with_data(Object::Marker, 1).delay_frames(1),
+        );
+        assert_eq!(update(&mut app), Vec::<u32>::new());
+        assert_eq!(update(&mut app), vec![1]);
+    }
+}
@@ -1,12 +1,35 @@
-use crate::events::{delay_spawn_events, ReadySpawnEvent, SpawnEvent};
-use crate::spawner::{Spawner, Spawners};
+use crate::budget::{
+    backlog_diagnostic, backlog_diagnostic_path, measure_backlog, SpawnBacklog, SpawnBudget,
+};
+use crate::despawner::{despawn_events, DespawnEvent, Despawner, DespawnerRegistry, Despawners};
+use crate::error::MissingPluginError;
+use crate::events::{
+    delay_spawn_events, ReadySpawnEvent, SpawnBatchEvent, SpawnEvent, SpawnFailed, SpawnRetries,
+    SpawnedEvent,
+};
+use crate::pending::{CancelSpawn, PauseSpawn, PendingSpawns, ResumeSpawn};
+use crate::pool::{prewarm_pools, PoolConfig, SpawnPools};
+use crate::spawn_point::RoundRobinCursor;
+use crate::spawner::{
+    spawn_batch_events, spawn_ready_events, DeferredSpawns, Spawner, SpawnerRegistry, Spawners,
+};
+use crate::spewable::{SpewVariants, Spewable};
+use crate::system::CachedSystem;
+use crate::wave::{direct_waves, StartWave, WaveCompleted, WaveStarted, WaveState};
+use crate::weighted::{weighted_spawns, SpewRng, WeightedSpawn};
+use bevy::diagnostic::{DiagnosticPath, RegisterDiagnostic};
+use bevy::ecs::schedule::{InternedScheduleLabel, ScheduleLabel};
 use bevy::prelude::*;
+use std::any::type_name;
+use std::fmt::Debug;
 
 #[allow(clippy::needless_doctest_main)]
 /// A plugin that enables spawning objects of type `T` while providing data of type `D`.
 /// Using multiple combinations of `T` and `D` requires adding multiple instances of this plugin to an [`App`].
 /// If your spawn systems don't require any data, simply pass `()` as the `D` type.
 ///
+/// By default, all objects are spawned in [`Update`]. Use [`SpewPlugin::new`] to spawn them in another schedule.
+///
 /// # Example
 /// ```rust,ignore
 /// use spew::prelude::*;
@@ -26,35 +49,368 @@ use bevy::prelude::*;
 /// ```
 pub struct SpewPlugin<T, D = ()>
 where
-    T: Eq + Send + Sync + 'static,
+    T: Debug + Eq + Send + Sync + 'static,
     D: Send + Sync + 'static,
 {
+    schedule: InternedScheduleLabel,
+    unhandled_policy: UnhandledSpawnPolicy,
+    budget: SpawnBudget,
+    clone_object: Option<fn(&T) -> T>,
+    failed_spawns: Option<FailedSpawns<T, D>>,
+    validation: Option<(fn() -> Vec<T>, ValidationPolicy)>,
+    #[cfg(feature = "assets")]
+    spawn_tables: Option<fn(&mut App, InternedScheduleLabel)>,
     _spawner_enum_type: std::marker::PhantomData<T>,
     _data_type: std::marker::PhantomData<D>,
 }
 
 impl<T, D> Default for SpewPlugin<T, D>
 where
-    T: Eq + Send + Sync + 'static,
+    T: Debug + Eq + Send + Sync + 'static,
     D: Send + Sync + 'static,
 {
     fn default() -> Self {
+        Self::new(Update)
+    }
+}
+
+impl<T, D> SpewPlugin<T, D>
+where
+    T: Debug + Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    /// Create a plugin that spawns objects in the given schedule.
+    /// All spawners registered for this combination of `T` and `D` run in that schedule.
+    /// Delays are counted in runs of the schedule and measured with [`Time`], which is [`Time<Fixed>`] inside of [`FixedUpdate`] and [`Time<Virtual>`] everywhere else.
+    ///
+    /// # Example
+    /// ```rust
+    /// use spew::prelude::*;
+    /// use bevy::prelude::*;
+    ///
+    /// #[derive(Debug, Eq, PartialEq)]
+    /// enum Projectile {
+    ///    Bullet
+    /// }
+    ///
+    /// App::new().add_plugins(SpewPlugin::<Projectile, Transform>::new(FixedUpdate));
+    /// ```
+    pub fn new(schedule: impl ScheduleLabel) -> Self {
         Self {
+            schedule: schedule.intern(),
+            unhandled_policy: default(),
+            budget: default(),
+            clone_object: None,
+            failed_spawns: None,
+            validation: None,
+            #[cfg(feature = "assets")]
+            spawn_tables: None,
             _spawner_enum_type: std::marker::PhantomData,
             _data_type: std::marker::PhantomData,
         }
     }
+
+    /// Set what happens when an object is requested for which no spawner was registered.
+    /// The default is [`UnhandledSpawnPolicy::WarnOnce`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use spew::prelude::*;
+    /// use bevy::prelude::*;
+    ///
+    /// #[derive(Debug, Eq, PartialEq)]
+    /// enum Object {
+    ///    Cube
+    /// }
+    ///
+    /// App::new().add_plugins(SpewPlugin::<Object>::default().unhandled_spawns(UnhandledSpawnPolicy::Panic));
+    /// ```
+    pub fn unhandled_spawns(mut self, policy: UnhandledSpawnPolicy) -> Self {
+        self.unhandled_policy = policy;
+        self
+    }
+
+    /// Limit how many objects are spawned per run of [`SpewSystemSet`], so that a burst of [`SpawnEvent`]s doesn't stall a single frame.
+    /// Objects over the budget are carried over to the following runs in the order they became due. The default is [`SpawnBudget::Unlimited`].
+    ///
+    /// The budget applies to [`SpawnEvent`]s, including the objects spawners request with [`SpewCommandsExt`](crate::prelude::SpewCommandsExt) while running.
+    /// [`SpawnBatchEvent`]s and objects spawned with [`SpewCommandsExt`](crate::prelude::SpewCommandsExt) outside of a spawner are not limited.
+    /// The size of the backlog is reported in the [`Diagnostic`](bevy::diagnostic::Diagnostic) at [`SpewPlugin::backlog_diagnostic`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use spew::prelude::*;
+    /// use bevy::prelude::*;
+    /// use std::time::Duration;
+    ///
+    /// #[derive(Debug, Eq, PartialEq)]
+    /// enum Object {
+    ///    Bullet
+    /// }
+    ///
+    /// App::new().add_plugins(SpewPlugin::<Object, Transform>::default().spawn_budget(SpawnBudget::Time(Duration::from_millis(2))));
+    /// ```
+    pub fn spawn_budget(mut self, budget: SpawnBudget) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// The path of the [`Diagnostic`](bevy::diagnostic::Diagnostic) that reports how many due objects are waiting in the backlog because of the [`SpawnBudget`].
+    /// It is measured every time [`SpewSystemSet`] runs and can be shown with e.g. the [`LogDiagnosticsPlugin`](bevy::diagnostic::LogDiagnosticsPlugin).
+    ///
+    /// # Example
+    /// ```rust
+    /// use spew::prelude::*;
+    /// use bevy::prelude::*;
+    /// use bevy::diagnostic::DiagnosticsStore;
+    ///
+    /// #[derive(Debug, Eq, PartialEq)]
+    /// enum Object {
+    ///    Bullet
+    /// }
+    ///
+    /// fn warn_about_backlog(diagnostics: Res<DiagnosticsStore>) {
+    ///     let path = SpewPlugin::<Object>::backlog_diagnostic();
+    ///     if let Some(backlog) = diagnostics.get(&path).and_then(|diagnostic| diagnostic.value()) {
+    ///         if backlog > 1000.0 {
+    ///             warn!("{backlog} bullets are waiting to be spawned");
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn backlog_diagnostic() -> DiagnosticPath {
+        backlog_diagnostic_path::<T, D>()
+    }
+}
+
+impl<T, D> SpewPlugin<T, D>
+where
+    T: Clone + Debug + Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    /// Record the object each root entity was spawned for in its [`Spewed`](crate::prelude::Spewed) component.
+    /// This is disabled by default because it clones the object for every spawned entity.
+    ///
+    /// # Example
+    /// ```rust
+    /// use spew::prelude::*;
+    /// use bevy::prelude::*;
+    ///
+    /// #[derive(Debug, Eq, PartialEq, Clone)]
+    /// enum Object {
+    ///    Monster { level: u32 }
+    /// }
+    ///
+    /// App::new().add_plugins(SpewPlugin::<Object>::default().track_origin());
+    ///
+    /// fn list_monsters(spewed: Query<&Spewed<Object>>) {
+    ///     for spewed in &spewed {
+    ///         if let Some(Object::Monster { level }) = spewed.object {
+    ///             info!("Found a level {level} monster spawned in frame {}", spewed.frame);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn track_origin(mut self) -> Self {
+        self.clone_object = Some(T::clone);
+        self
+    }
+}
+
+impl<T, D> SpewPlugin<T, D>
+where
+    T: Clone + Debug + Eq + Send + Sync + 'static,
+    D: Clone + Send + Sync + 'static,
+{
+    /// Set what happens when a fallible spawner fails. By default, failed spawns are dropped after sending a [`SpawnFailed`] event.
+    /// Setting a policy keeps a copy of the data of every spawn so that it can be retried and included in the [`SpawnFailed`] event.
+    ///
+    /// # Example
+    /// ```rust
+    /// use spew::prelude::*;
+    /// use bevy::prelude::*;
+    ///
+    /// #[derive(Debug, Eq, PartialEq, Clone)]
+    /// enum Object {
+    ///    Monster
+    /// }
+    ///
+    /// App::new().add_plugins(SpewPlugin::<Object, Transform>::default().failed_spawns(RetryPolicy::NextFrame { max_retries: 3 }));
+    /// ```
+    pub fn failed_spawns(mut self, retry: RetryPolicy) -> Self {
+        self.failed_spawns = Some(FailedSpawns {
+            retry,
+            clone_object: T::clone,
+            clone_data: D::clone,
+        });
+        self
+    }
+}
+
+#[cfg(feature = "assets")]
+impl<T, D> SpewPlugin<T, D>
+where
+    T: Clone + Debug + Eq + serde::de::DeserializeOwned + Send + Sync + 'static,
+    D: Clone + serde::de::DeserializeOwned + Send + Sync + 'static,
+{
+    /// Enable loading [`SpawnTable`](crate::prelude::SpawnTable)s from `.spawn.ron` files and playing them with [`PlaySpawnTable`](crate::prelude::PlaySpawnTable) events.
+    /// Requires the `assets` feature and the [`AssetPlugin`](bevy::asset::AssetPlugin), which is part of the `DefaultPlugins`, to be added first.
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// use spew::prelude::*;
+    /// use bevy::prelude::*;
+    ///
+    /// #[derive(Debug, Eq, PartialEq, Clone, serde::Deserialize)]
+    /// enum Enemy {
+    ///    Goblin
+    /// }
+    ///
+    /// App::new()
+    ///     .add_plugins(DefaultPlugins)
+    ///     .add_plugins(SpewPlugin::<Enemy, f32>::default().spawn_tables());
+    /// ```
+    pub fn spawn_tables(mut self) -> Self {
+        self.spawn_tables = Some(crate::spawn_table::add_spawn_tables::<T, D>);
+        self
+    }
+}
+
+impl<T, D> SpewPlugin<T, D>
+where
+    T: SpewVariants + Debug + Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    /// Check that every variant listed by [`SpewVariants`] has a spawner for this plugin's data type `D` once the app is built, and that it is clear which one runs.
+    /// Variants without a spawner would not be spawned. A spawner registered for the variant itself takes precedence over spawners registered with a [`Matcher`](crate::prelude::Matcher),
+    /// so only multiple spawners for the variant itself, or multiple matching matchers without such a spawner, are reported, since of those only the first one registered runs.
+    ///
+    /// # Example
+    /// ```rust
+    /// use spew::prelude::*;
+    /// use bevy::prelude::*;
+    ///
+    /// #[derive(Debug, Eq, PartialEq)]
+    /// enum Object {
+    ///    Cube
+    /// }
+    ///
+    /// impl SpewVariants for Object {
+    ///     fn variants() -> Vec<Self> {
+    ///         vec![Object::Cube]
+    ///     }
+    /// }
+    ///
+    /// App::new()
+    ///     .add_plugins(SpewPlugin::<Object>::default().validate_variants(ValidationPolicy::Panic))
+    ///     .add_spawner((Object::Cube, spawn_cube));
+    ///
+    /// fn spawn_cube(mut commands: Commands) {
+    ///     commands.spawn(Name::new("Cube"));
+    /// }
+    /// ```
+    pub fn validate_variants(mut self, policy: ValidationPolicy) -> Self {
+        self.validation = Some((T::variants, policy));
+        self
+    }
 }
 
 impl<T, D> Plugin for SpewPlugin<T, D>
 where
-    T: Eq + Send + Sync + 'static,
+    T: Debug + Eq + Send + Sync + 'static,
     D: Send + Sync + 'static,
 {
     fn build(&self, app: &mut App) {
-        app.add_event::<SpawnEvent<T, D>>()
-            .add_event::<ReadySpawnEvent<T, D>>()
-            .add_systems(Update, delay_spawn_events::<T, D>.in_set(SpewSystemSet));
+        app.insert_resource(SpewConfig::<T, D> {
+            unhandled_policy: self.unhandled_policy,
+            budget: self.budget,
+            clone_object: self.clone_object,
+            failed_spawns: self.failed_spawns,
+            _spawner_enum_type: std::marker::PhantomData,
+            _data_type: std::marker::PhantomData,
+        })
+        .add_event::<SpawnEvent<T, D>>()
+        .add_event::<ReadySpawnEvent<T, D>>()
+        .add_event::<SpawnBatchEvent<T, D>>()
+        .add_event::<WeightedSpawn<T, D>>()
+        .add_event::<StartWave<T, D>>()
+        .add_event::<WaveStarted<T, D>>()
+        .add_event::<WaveCompleted<T, D>>()
+        .add_event::<SpawnedEvent<T, D>>()
+        .add_event::<SpawnFailed<T, D>>()
+        .add_event::<CancelSpawn>()
+        .add_event::<PauseSpawn>()
+        .add_event::<ResumeSpawn>()
+        .init_resource::<PendingSpawns<T, D>>()
+        .init_resource::<SpawnRetries<T, D>>()
+        .init_resource::<SpawnBacklog<T, D>>()
+        .register_diagnostic(backlog_diagnostic::<T, D>())
+        .init_resource::<SpawnerRegistry<T, D>>()
+        .init_resource::<DeferredSpawns>()
+        .init_resource::<SpewRng>()
+        .init_resource::<RoundRobinCursor<T>>()
+        .init_resource::<WaveState<T, D>>()
+        .add_systems(
+            self.schedule,
+            (
+                weighted_spawns::<T, D>,
+                direct_waves::<T, D>,
+                delay_spawn_events::<T, D>,
+                spawn_ready_events::<T, D>,
+                measure_backlog::<T, D>,
+                spawn_batch_events::<T, D>,
+            )
+                .chain()
+                .in_set(SpewSystemSet),
+        );
+        #[cfg(feature = "assets")]
+        if let Some(add_spawn_tables) = self.spawn_tables {
+            add_spawn_tables(app, self.schedule);
+        }
+        // Despawners are shared by all plugins for `T`, so only the first one sets them up.
+        if !app.world().contains_resource::<DespawnerRegistry<T>>() {
+            app.add_event::<DespawnEvent<T>>()
+                .init_resource::<DespawnerRegistry<T>>()
+                .add_systems(
+                    self.schedule,
+                    despawn_events::<T>
+                        .before(delay_spawn_events::<T, D>)
+                        .in_set(SpewSystemSet),
+                );
+        }
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Some((variants, policy)) = self.validation else {
+            return;
+        };
+        let registry = app.world().resource::<SpawnerRegistry<T, D>>();
+        let problems: Vec<_> = variants()
+            .into_iter()
+            .filter_map(|variant| match registry.count(&variant) {
+                (0, 0) => Some(format!("{variant:?} has no spawner")),
+                (0, matched) if matched > 1 => Some(format!(
+                    "{variant:?} is matched by {matched} matchers, of which only the first one registered is used"
+                )),
+                (exact, _) if exact > 1 => Some(format!(
+                    "{variant:?} has {exact} spawners, of which only the first one registered is used"
+                )),
+                _ => None,
+            })
+            .collect();
+        if problems.is_empty() {
+            return;
+        }
+        let message = format!(
+            "Invalid spawners for `SpewPlugin::<{object_type}, {data}>`: {problems}",
+            object_type = type_name::<T>(),
+            data = type_name::<D>(),
+            problems = problems.join("; "),
+        );
+        match policy {
+            ValidationPolicy::Warn => warn!("{message}"),
+            ValidationPolicy::Panic => panic!("{message}"),
+        }
     }
 
     fn is_unique(&self) -> bool {
@@ -62,6 +418,100 @@ where
     }
 }
 
+/// What to do when [`SpewPlugin::validate_variants`] finds variants without a spawner or with multiple spawners of which only the first one runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ValidationPolicy {
+    /// Log a warning.
+    #[default]
+    Warn,
+    /// Panic.
+    Panic,
+}
+
+/// What to do when a [`SpawnEvent`] requests an object that has no registered spawner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnhandledSpawnPolicy {
+    /// Silently drop the event.
+    Ignore,
+    /// Log a warning the first time each object is requested without a spawner.
+    #[default]
+    WarnOnce,
+    /// Log a warning every time an object is requested without a spawner.
+    Warn,
+    /// Panic.
+    Panic,
+}
+
+/// What to do when a fallible spawner fails. See [`SpewPlugin::failed_spawns`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RetryPolicy {
+    /// Don't try again.
+    #[default]
+    Drop,
+    /// Try again in the next run of [`SpewSystemSet`], up to `max_retries` times.
+    NextFrame {
+        /// How many times to try again after the first attempt failed.
+        max_retries: u32,
+    },
+}
+
+impl RetryPolicy {
+    /// Whether to try again after the given number of failed attempts.
+    pub(crate) fn allows(&self, failed_attempts: u32) -> bool {
+        match self {
+            RetryPolicy::Drop => false,
+            RetryPolicy::NextFrame { max_retries } => failed_attempts <= *max_retries,
+        }
+    }
+}
+
+/// How failed spawns are handled, including the clone functions needed to keep a copy of the request.
+pub(crate) struct FailedSpawns<T, D> {
+    pub(crate) retry: RetryPolicy,
+    pub(crate) clone_object: fn(&T) -> T,
+    pub(crate) clone_data: fn(&D) -> D,
+}
+
+impl<T, D> Clone for FailedSpawns<T, D> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, D> Copy for FailedSpawns<T, D> {}
+
+/// The configuration of a single [`SpewPlugin`].
+#[derive(Resource)]
+pub(crate) struct SpewConfig<T, D>
+where
+    T: Debug + Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    pub(crate) unhandled_policy: UnhandledSpawnPolicy,
+    pub(crate) budget: SpawnBudget,
+    pub(crate) clone_object: Option<fn(&T) -> T>,
+    pub(crate) failed_spawns: Option<FailedSpawns<T, D>>,
+    _spawner_enum_type: std::marker::PhantomData<T>,
+    _data_type: std::marker::PhantomData<D>,
+}
+
+impl<T, D> Clone for SpewConfig<T, D>
+where
+    T: Debug + Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, D> Copy for SpewConfig<T, D>
+where
+    T: Debug + Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+}
+
 /// The SystemSet that contains all spew systems.
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemSet)]
 pub struct SpewSystemSet;
@@ -69,6 +519,10 @@ pub struct SpewSystemSet;
 /// A trait that allows adding spawners to an [`App`].
 /// Spawners are tuples of an object and a spawning function, e.g. `(Object::Cube, spawn_cube)`.
 /// A spawning function has the same signature as a bevy system function, where user provided data is passed as an `In<D>` parameter in the first position.
+/// It may return the [`Entity`] or `Vec<Entity>` it spawned, which will then be reported in a [`SpawnedEvent`].
+/// Like a system, each spawning function keeps its state between runs, so `Local` parameters and change detection work as expected.
+/// Instead of a single object, a spawner can also handle all objects matching a pattern created with [`spew::matches!`](crate::matches).
+/// See [`Matcher`](crate::prelude::Matcher) for more information.
 ///
 /// The spawner's combination of object enum and user data must have been registered with an own [`SpewPlugin`] beforehand.
 pub trait SpewApp {
@@ -145,6 +599,125 @@ pub trait SpewApp {
     fn add_spawners<T, D>(&mut self, spawners: T) -> &mut App
     where
         T: Spawners<D>;
+
+    /// Add the spawners of all variants of a [`Spewable`] enum to the app.
+    /// This is not limited in the number of spawners like [`SpewApp::add_spawners`].
+    ///
+    /// # Example
+    /// ```rust,ignore
+    /// use spew::prelude::*;
+    /// use bevy::prelude::*;
+    ///
+    /// #[derive(Debug, Eq, PartialEq, Spewable)]
+    /// enum Object {
+    ///   #[spawner(spawn_cube)]
+    ///   Cube,
+    /// }
+    ///
+    /// fn main() {
+    ///     App::new()
+    ///         .add_plugins(DefaultPlugins)
+    ///         .add_plugins(SpewPlugin::<Object, Transform>::default())
+    ///         .add_spewable::<Object>()
+    ///         .run();
+    /// }
+    ///
+    /// fn spawn_cube(In(transform): In<Transform>, mut commands: Commands) {
+    ///    commands.spawn((Name::new("Cube"), transform));
+    /// }
+    /// ```
+    fn add_spewable<T>(&mut self) -> &mut App
+    where
+        T: Spewable;
+
+    /// Add a single despawner to the app.
+    /// Despawners are tuples of an object and a despawning function, e.g. `(Object::Monster, drop_loot)`.
+    /// A despawning function has the same signature as a bevy system function, where the entity to despawn is passed as an `In<Entity>` parameter in the first position.
+    ///
+    /// When a [`DespawnEvent`] is sent for an entity, the despawner registered for the enum variant in its [`Spewed`](crate::prelude::Spewed) component runs
+    /// before the entity is despawned recursively. The fields of the variants are not compared, so there is one despawner per variant.
+    /// If another despawner is registered for the same variant, only the first one registered is used and a warning is logged.
+    ///
+    /// # Example
+    /// ```rust
+    /// use spew::prelude::*;
+    /// use bevy::prelude::*;
+    ///
+    /// #[derive(Debug, Eq, PartialEq)]
+    /// enum Object {
+    ///   Monster
+    /// }
+    ///
+    /// App::new()
+    ///     .add_plugins(SpewPlugin::<Object, Transform>::default())
+    ///     .add_despawner((Object::Monster, drop_loot));
+    ///
+    /// fn drop_loot(In(entity): In<Entity>, transforms: Query<&Transform>, mut commands: Commands) {
+    ///     let transform = transforms.get(entity).copied().unwrap_or_default();
+    ///     commands.spawn((Name::new("Loot"), transform));
+    /// }
+    /// ```
+    fn add_despawner<T, D>(&mut self, despawner: T) -> &mut App
+    where
+        T: Despawner<D>;
+
+    /// Add multiple despawners to the app by providing them in a tuple.
+    /// See [`SpewApp::add_despawner`] for more information.
+    fn add_despawners<T, D>(&mut self, despawners: T) -> &mut App
+    where
+        T: Despawners<D>;
+
+    /// Keep a pool of entities for an object so that they can be reused instead of being spawned and despawned over and over again.
+    /// The object's spawner is still needed to fill the pool: it runs with `D::default()` to prewarm the pool on [`Startup`]
+    /// and with the requested data whenever the pool has to grow.
+    /// When a pooled entity is available, a [`SpawnEvent`] for the object runs the reset function instead,
+    /// which receives the pooled root entity and the event's data in an `In<(Entity, D)>` parameter.
+    ///
+    /// Return an entity to its pool with a [`DespawnEvent`] or [`SpewCommandsExt::recycle`](crate::prelude::SpewCommandsExt::recycle).
+    /// Pooled entities waiting to be reused are detached from their parent and marked with [`InPool`](crate::prelude::InPool).
+    /// Only the first root entity created by the spawner is pooled, so spawners of pooled objects should spawn a single hierarchy.
+    ///
+    /// # Example
+    /// ```rust
+    /// use spew::prelude::*;
+    /// use bevy::prelude::*;
+    ///
+    /// #[derive(Debug, Eq, PartialEq)]
+    /// enum Projectile {
+    ///    Bullet
+    /// }
+    ///
+    /// #[derive(Component)]
+    /// struct Bullet;
+    ///
+    /// App::new()
+    ///     .add_plugins(SpewPlugin::<Projectile, Transform>::default())
+    ///     .add_spawner((Projectile::Bullet, spawn_bullet))
+    ///     .add_pool(
+    ///         Projectile::Bullet,
+    ///         reset_bullet,
+    ///         PoolConfig::default().prewarm(100).max_size(1000),
+    ///     );
+    ///
+    /// fn spawn_bullet(In(transform): In<Transform>, mut commands: Commands) -> Entity {
+    ///     commands.spawn((Name::new("Bullet"), Bullet, transform)).id()
+    /// }
+    ///
+    /// fn reset_bullet(In((entity, transform)): In<(Entity, Transform)>, mut commands: Commands) {
+    ///     commands.entity(entity).insert(transform);
+    /// }
+    /// ```
+    fn add_pool<T, D, F, Marker>(
+        &mut self,
+        object: T,
+        reset_function: F,
+        config: PoolConfig,
+    ) -> &mut App
+    where
+        T: Debug + Eq + Send + Sync + 'static,
+        D: Default + Send + Sync + 'static,
+        F: SystemParamFunction<Marker, In = (Entity, D), Out = ()>,
+        Marker: Send + Sync + 'static;
 }
 
 impl SpewApp for App {
@@ -162,4 +735,120 @@ impl SpewApp for App {
         spawners.add_to_app(self);
         self
     }
+
+    fn add_spewable<T>(&mut self) -> &mut App
+    where
+        T: Spewable,
+    {
+        T::add_spawners(self);
+        self
+    }
+
+    fn add_despawner<T, D>(&mut self, despawner: T) -> &mut App
+    where
+        T: Despawner<D>,
+    {
+        despawner.add_to_app(self);
+        self
+    }
+
+    fn add_despawners<T, D>(&mut self, despawners: T) -> &mut App
+    where
+        T: Despawners<D>,
+    {
+        despawners.add_to_app(self);
+        self
+    }
+
+    fn add_pool<T, D, F, Marker>(
+        &mut self,
+        object: T,
+        reset_function: F,
+        config: PoolConfig,
+    ) -> &mut App
+    where
+        T: Debug + Eq + Send + Sync + 'static,
+        D: Default + Send + Sync + 'static,
+        F: SystemParamFunction<Marker, In = (Entity, D), Out = ()>,
+        Marker: Send + Sync + 'static,
+    {
+        if !self.world().contains_resource::<SpawnerRegistry<T, D>>() {
+            panic!("{}", MissingPluginError::new::<T, D>(&object));
+        }
+        if !self.world().contains_resource::<SpawnPools<T, D>>() {
+            self.init_resource::<SpawnPools<T, D>>()
+                .add_systems(Startup, prewarm_pools::<T, D>);
+        }
+        let mut reset_function = CachedSystem::new(reset_function);
+        let reset = move |world: &mut World, entity: Entity, user_data: D| {
+            reset_function.run(world, (entity, user_data));
+        };
+        self.world_mut()
+            .resource_mut::<SpawnPools<T, D>>()
+            .insert(object, config);
+        self.world_mut()
+            .resource_mut::<SpawnerRegistry<T, D>>()
+            .insert_reset_function(Box::new(reset));
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use bevy::prelude::*;
+
+    #[derive(Debug, Eq, PartialEq, Clone)]
+    enum Object {
+        Cube,
+        Ball,
+    }
+
+    impl SpewVariants for Object {
+        fn variants() -> Vec<Self> {
+            vec![Object::Cube, Object::Ball]
+        }
+    }
+
+    fn spawn(mut commands: Commands) {
+        commands.spawn_empty();
+    }
+
+    fn spawn_matched(In((_object, ())): In<(Object, ())>, mut commands: Commands) {
+        commands.spawn_empty();
+    }
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins).add_plugins(
+            SpewPlugin::<Object>::default().validate_variants(ValidationPolicy::Panic),
+        );
+        app
+    }
+
+    #[test]
+    fn exact_spawner_takes_precedence_over_matchers() {
+        let mut app = app();
+        app.add_spawners((
+            (Object::Cube, spawn),
+            (crate::matches!(Object::Cube | Object::Ball), spawn_matched),
+            (crate::matches!(Object::Cube), spawn_matched),
+        ));
+        app.finish();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Cube has 2 spawners, of which only the first one registered is used; Ball is matched by 2 matchers, of which only the first one registered is used"
+    )]
+    fn ambiguous_spawners() {
+        let mut app = app();
+        app.add_spawners((
+            (Object::Cube, spawn),
+            (Object::Cube, spawn),
+            (crate::matches!(Object::Ball), spawn_matched),
+            (crate::matches!(Object::Ball | Object::Cube), spawn_matched),
+        ));
+        app.finish();
+    }
 }
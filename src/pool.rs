@@ -0,0 +1,457 @@
+use crate::spawner::{with_registry, SpawnRequest, SpawnerRegistry};
+use crate::tracking::SpawnTracker;
+use bevy::core::FrameCount;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::mem::{self, Discriminant};
+
+/// The configuration of an object pool. See [`SpewApp::add_pool`](crate::prelude::SpewApp::add_pool) for more information.
+///
+/// # Example
+/// ```rust
+/// use spew::prelude::*;
+///
+/// let config = PoolConfig::default()
+///     .prewarm(64)
+///     .max_size(512)
+///     .overflow(PoolOverflow::RecycleOldest);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PoolConfig {
+    /// How many entities to spawn into the pool on [`Startup`].
+    pub prewarm: usize,
+    /// The maximum number of entities the pool manages, including the ones that are currently in use. `None` means no limit.
+    pub max_size: Option<usize>,
+    /// What to do when a spawn is requested while all entities of a full pool are in use.
+    pub overflow: PoolOverflow,
+}
+
+impl PoolConfig {
+    /// Set how many entities to spawn into the pool on [`Startup`].
+    pub fn prewarm(mut self, count: usize) -> Self {
+        self.prewarm = count;
+        self
+    }
+
+    /// Set the maximum number of entities the pool manages, including the ones that are currently in use.
+    pub fn max_size(mut self, max_size: usize) -> Self {
+        self.max_size = Some(max_size);
+        self
+    }
+
+    /// Set what to do when a spawn is requested while all entities of a full pool are in use.
+    pub fn overflow(mut self, overflow: PoolOverflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+}
+
+/// How a pool grows once it has reached its [`PoolConfig::max_size`] and all of its entities are in use.
+/// Until then, the pool grows by spawning a new entity with the regular spawner whenever no pooled entity is available.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PoolOverflow {
+    /// Spawn a new entity with the regular spawner that is not part of the pool.
+    #[default]
+    SpawnUnpooled,
+    /// Don't spawn anything.
+    Skip,
+    /// Reset and reuse the entity that has been in use for the longest time.
+    RecycleOldest,
+}
+
+/// A marker component for entities that are waiting in a pool to be reused.
+/// Pooled entities stay in the world while they are not in use, so exclude them from your queries with `Without<InPool>`.
+/// With the `bevy_render` feature, they are also hidden.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct InPool;
+
+/// Marks the root entity of a pooled object and remembers how to return it to its pool.
+#[derive(Component)]
+pub(crate) struct Pooled {
+    pool: usize,
+    release: fn(&mut World, Entity, usize),
+}
+
+/// All pools registered for a combination of object and user data.
+#[derive(Resource)]
+pub(crate) struct SpawnPools<T, D>
+where
+    T: Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    lookup: HashMap<Discriminant<T>, Vec<(T, usize)>>,
+    pools: Vec<Pool>,
+    _data_type: std::marker::PhantomData<D>,
+}
+
+struct Pool {
+    config: PoolConfig,
+    available: Vec<Entity>,
+    /// Entities that were taken out of the pool, in the order they were taken out.
+    in_use: VecDeque<Entity>,
+}
+
+/// What to do with a spawn request for a pooled object.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum PoolAction {
+    /// Reset and reuse a pooled entity.
+    Reuse { pool: usize, entity: Entity },
+    /// Spawn a new entity and add it to the pool.
+    Grow { pool: usize },
+    /// Spawn a new entity that is not pooled.
+    Spawn,
+    /// Don't spawn anything.
+    Skip,
+}
+
+impl<T, D> Default for SpawnPools<T, D>
+where
+    T: Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self {
+            lookup: default(),
+            pools: default(),
+            _data_type: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<T, D> SpawnPools<T, D>
+where
+    T: Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    /// Add a pool and return its index.
+    pub(crate) fn insert(&mut self, object: T, config: PoolConfig) -> usize {
+        let pool = self.pools.len();
+        self.pools.push(Pool {
+            config,
+            available: default(),
+            in_use: default(),
+        });
+        self.lookup
+            .entry(mem::discriminant(&object))
+            .or_default()
+            .push((object, pool));
+        pool
+    }
+
+    pub(crate) fn find(&self, object: &T) -> Option<usize> {
+        self.lookup
+            .get(&mem::discriminant(object))?
+            .iter()
+            .find_map(|(candidate, pool)| (candidate == object).then_some(*pool))
+    }
+
+    fn acquire(&mut self, pool_index: usize, world: &World) -> PoolAction {
+        let pool = &mut self.pools[pool_index];
+        let exists = |entity: &Entity| world.get_entity(*entity).is_some();
+        while let Some(entity) = pool.available.pop() {
+            if exists(&entity) {
+                pool.in_use.push_back(entity);
+                return PoolAction::Reuse {
+                    pool: pool_index,
+                    entity,
+                };
+            }
+        }
+        pool.in_use.retain(exists);
+        if pool
+            .config
+            .max_size
+            .is_none_or(|max_size| pool.in_use.len() < max_size)
+        {
+            return PoolAction::Grow { pool: pool_index };
+        }
+        match pool.config.overflow {
+            PoolOverflow::SpawnUnpooled => PoolAction::Spawn,
+            PoolOverflow::Skip => PoolAction::Skip,
+            PoolOverflow::RecycleOldest => match pool.in_use.pop_front() {
+                Some(entity) => {
+                    pool.in_use.push_back(entity);
+                    PoolAction::Reuse {
+                        pool: pool_index,
+                        entity,
+                    }
+                }
+                None => PoolAction::Grow { pool: pool_index },
+            },
+        }
+    }
+}
+
+/// Decide how to handle a spawn request for the given pool.
+pub(crate) fn acquire<T, D>(world: &mut World, pool: usize) -> PoolAction
+where
+    T: Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    world.resource_scope(|world, mut pools: Mut<SpawnPools<T, D>>| pools.acquire(pool, world))
+}
+
+/// Add a freshly spawned entity to a pool.
+/// If `in_use` is false, the entity goes straight into the pool, otherwise it is added to the entities that are in use.
+pub(crate) fn add_to_pool<T, D>(world: &mut World, pool: usize, entity: Entity, in_use: bool)
+where
+    T: Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    world.entity_mut(entity).insert(Pooled {
+        pool,
+        release: release::<T, D>,
+    });
+    let mut pools = world.resource_mut::<SpawnPools<T, D>>();
+    let pool = &mut pools.pools[pool];
+    if in_use {
+        pool.in_use.push_back(entity);
+    } else {
+        pool.available.push(entity);
+        deactivate(world, entity);
+    }
+}
+
+/// Prepare a pooled entity for being reused.
+/// Entities recycled by [`PoolOverflow::RecycleOldest`] are still in use, so they are detached from their old parent here.
+pub(crate) fn activate(world: &mut World, entity: Entity) {
+    let mut entity = world.entity_mut(entity);
+    entity.remove_parent().remove::<InPool>();
+    #[cfg(feature = "bevy_render")]
+    entity.insert(Visibility::Inherited);
+}
+
+fn deactivate(world: &mut World, entity: Entity) {
+    let mut entity = world.entity_mut(entity);
+    entity.remove_parent().insert(InPool);
+    #[cfg(feature = "bevy_render")]
+    entity.insert(Visibility::Hidden);
+}
+
+fn release<T, D>(world: &mut World, entity: Entity, pool: usize)
+where
+    T: Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    if world.entity(entity).contains::<InPool>() {
+        return;
+    }
+    let mut pools = world.resource_mut::<SpawnPools<T, D>>();
+    let pool = &mut pools.pools[pool];
+    pool.in_use.retain(|&in_use| in_use != entity);
+    pool.available.push(entity);
+    deactivate(world, entity);
+}
+
+/// Return a pooled entity to its pool, or despawn it recursively if it is not pooled.
+pub(crate) fn recycle(world: &mut World, entity: Entity) {
+    let Some(entity_ref) = world.get_entity(entity) else {
+        return;
+    };
+    match entity_ref.get::<Pooled>() {
+        Some(&Pooled { pool, release }) => release(world, entity, pool),
+        None => world.entity_mut(entity).despawn_recursive(),
+    }
+}
+
+pub(crate) fn prewarm_pools<T, D>(world: &mut World)
+where
+    T: Debug + Eq + Send + Sync + 'static,
+    D: Default + Send + Sync + 'static,
+{
+    let frame = world
+        .get_resource::<FrameCount>()
+        .map_or(0, |frame| frame.0);
+    with_registry(world, |world, registry: &mut SpawnerRegistry<T, D>| {
+        let pools = world.resource::<SpawnPools<T, D>>();
+        let prewarm_counts: Vec<_> = pools
+            .lookup
+            .values()
+            .flatten()
+            .map(|&(_, pool)| (pool, pools.pools[pool].config.prewarm))
+            .collect();
+        let mut tracker = SpawnTracker::default();
+        for (pool, count) in prewarm_counts {
+            for _ in 0..count {
+                tracker.start(world);
+                world.resource_scope(|world, pools: Mut<SpawnPools<T, D>>| {
+                    let object = pools
+                        .lookup
+                        .values()
+                        .flatten()
+                        .find_map(|(object, index)| (*index == pool).then_some(object));
+                    let Some(object) = object else {
+                        return;
+                    };
+                    if let Some(spawn_function) = registry.spawn_function_mut(object) {
+                        let request = SpawnRequest {
+                            object,
+                            frame,
+                            correlation_id: None,
+                            parent: None,
+                            spawn_point: None,
+                        };
+                        if let Err(error) = spawn_function(world, request, D::default()) {
+                            warn!("Failed to prewarm the pool for {object:?}: {error}");
+                        }
+                    }
+                });
+                let root = tracker
+                    .spawned_entities(world)
+                    .iter()
+                    .copied()
+                    .find(|&entity| !world.entity(entity).contains::<Parent>());
+                if let Some(root) = root {
+                    add_to_pool::<T, D>(world, pool, root, false);
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Pooled;
+    use crate::prelude::*;
+    use bevy::prelude::*;
+
+    #[derive(Debug, Eq, PartialEq)]
+    enum Projectile {
+        Bullet,
+    }
+
+    #[derive(Component)]
+    struct Bullet(u32);
+
+    fn spawn_bullet(In(id): In<u32>, mut commands: Commands) -> Entity {
+        commands.spawn(Bullet(id)).id()
+    }
+
+    fn reset_bullet(In((entity, id)): In<(Entity, u32)>, mut commands: Commands) {
+        commands.entity(entity).insert(Bullet(id));
+    }
+
+    fn app(config: PoolConfig) -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(SpewPlugin::<Projectile, u32>::default())
+            .add_spawner((Projectile::Bullet, spawn_bullet))
+            .add_pool(Projectile::Bullet, reset_bullet, config);
+        app
+    }
+
+    /// Spawn a bullet in the next update and return the entity it ended up in.
+    fn shoot(app: &mut App, event: SpawnEvent<Projectile, u32>) -> Option<Entity> {
+        let id = event.data;
+        app.world_mut().send_event(event);
+        app.update();
+        let world = app.world_mut();
+        world
+            .query_filtered::<(Entity, &Bullet), Without<InPool>>()
+            .iter(world)
+            .find_map(|(entity, bullet)| (bullet.0 == id).then_some(entity))
+    }
+
+    fn recycle(app: &mut App, entity: Entity) {
+        app.world_mut().commands().recycle(entity);
+        app.world_mut().flush();
+    }
+
+    /// The ids of the bullets in use, ordered by their entity, and the number of bullets waiting in the pool.
+    fn bullets(app: &mut App) -> (Vec<u32>, usize) {
+        let world = app.world_mut();
+        let mut in_use: Vec<_> = world
+            .query_filtered::<(Entity, &Bullet), Without<InPool>>()
+            .iter(world)
+            .map(|(entity, bullet)| (entity, bullet.0))
+            .collect();
+        in_use.sort_unstable();
+        let in_pool = world
+            .query_filtered::<(), (With<Bullet>, With<InPool>)>()
+            .iter(world)
+            .count();
+        (in_use.into_iter().map(|(_, id)| id).collect(), in_pool)
+    }
+
+    #[test]
+    fn grow_until_max_size_then_spawn_unpooled() {
+        let mut app = app(PoolConfig::default().max_size(2));
+        let first = shoot(&mut app, SpawnEvent::with_data(Projectile::Bullet, 1)).unwrap();
+        let second = shoot(&mut app, SpawnEvent::with_data(Projectile::Bullet, 2)).unwrap();
+        let third = shoot(&mut app, SpawnEvent::with_data(Projectile::Bullet, 3)).unwrap();
+        assert_eq!(bullets(&mut app), (vec![1, 2, 3], 0));
+        assert!(app.world().entity(first).contains::<Pooled>());
+        assert!(app.world().entity(second).contains::<Pooled>());
+        assert!(!app.world().entity(third).contains::<Pooled>());
+
+        // Pooled bullets go back into the pool, the unpooled one is despawned
+        for bullet in [first, second, third] {
+            recycle(&mut app, bullet);
+        }
+        assert_eq!(bullets(&mut app), (vec![], 2));
+        assert!(app.world().get_entity(third).is_none());
+    }
+
+    #[test]
+    fn skip_when_full() {
+        let mut app = app(PoolConfig::default()
+            .max_size(1)
+            .overflow(PoolOverflow::Skip));
+        assert!(shoot(&mut app, SpawnEvent::with_data(Projectile::Bullet, 1)).is_some());
+        assert!(shoot(&mut app, SpawnEvent::with_data(Projectile::Bullet, 2)).is_none());
+        assert_eq!(bullets(&mut app), (vec![1], 0));
+    }
+
+    #[test]
+    fn recycle_oldest_when_full() {
+        let mut app = app(PoolConfig::default()
+            .max_size(2)
+            .overflow(PoolOverflow::RecycleOldest));
+        let old_gun = app.world_mut().spawn_empty().id();
+        let new_gun = app.world_mut().spawn_empty().id();
+        let oldest = shoot(
+            &mut app,
+            SpawnEvent::with_data(Projectile::Bullet, 1).with_parent(old_gun),
+        )
+        .unwrap();
+        shoot(&mut app, SpawnEvent::with_data(Projectile::Bullet, 2));
+        let recycled = shoot(
+            &mut app,
+            SpawnEvent::with_data(Projectile::Bullet, 3).with_parent(new_gun),
+        )
+        .unwrap();
+        assert_eq!(recycled, oldest);
+        assert_eq!(bullets(&mut app), (vec![3, 2], 0));
+        // The bullet is still in use, so it has to be moved from its old parent to the new one
+        assert!(app.world().get::<Children>(old_gun).is_none());
+        assert_eq!(
+            app.world().get::<Parent>(recycled).map(Parent::get),
+            Some(new_gun)
+        );
+    }
+
+    #[test]
+    fn prewarm_pool() {
+        let mut app = app(PoolConfig::default().prewarm(3));
+        app.update();
+        assert_eq!(bullets(&mut app), (vec![], 3));
+        shoot(&mut app, SpawnEvent::with_data(Projectile::Bullet, 1));
+        assert_eq!(bullets(&mut app), (vec![1], 2));
+    }
+
+    #[test]
+    fn reuse_recycled_entity() {
+        let mut app = app(PoolConfig::default());
+        let bullet = shoot(&mut app, SpawnEvent::with_data(Projectile::Bullet, 1)).unwrap();
+        recycle(&mut app, bullet);
+        assert!(app.world().entity(bullet).contains::<InPool>());
+        assert_eq!(bullets(&mut app), (vec![], 1));
+
+        let reused = shoot(&mut app, SpawnEvent::with_data(Projectile::Bullet, 2)).unwrap();
+        assert_eq!(reused, bullet);
+        assert!(!app.world().entity(bullet).contains::<InPool>());
+        assert_eq!(bullets(&mut app), (vec![2], 0));
+    }
+}
@@ -0,0 +1,330 @@
+use crate::error::SpawnPointError;
+use crate::spawner::Matcher;
+use crate::weighted::SpewRng;
+use bevy::prelude::*;
+use std::fmt::Debug;
+
+/// A component that marks an entity as a place where objects of type `T` can be spawned.
+/// Request a spawn at one of these with [`SpawnEvent::at_spawn_point`](crate::prelude::SpawnEvent::at_spawn_point).
+/// Spew resolves the [`GlobalTransform`] of the selected spawn point and passes it to spawners wrapped with [`with_context`](crate::prelude::with_context) in [`SpawnContext::spawn_point`](crate::prelude::SpawnContext::spawn_point).
+///
+/// # Example
+/// ```rust
+/// use spew::prelude::*;
+/// use bevy::prelude::*;
+///
+/// #[derive(Debug, Eq, PartialEq)]
+/// enum Enemy {
+///    Goblin,
+///    Dragon,
+/// }
+///
+/// fn setup_level(mut commands: Commands) {
+///     // Any enemy can spawn here
+///     commands.spawn((SpawnPoint::<Enemy>::default(), TransformBundle::from_transform(Transform::from_xyz(1.0, 0.0, 0.0))));
+///     // Only dragons can spawn here
+///     commands.spawn((
+///         SpawnPoint::only(spew::matches!(Enemy::Dragon)),
+///         TransformBundle::from_transform(Transform::from_xyz(10.0, 0.0, 0.0)),
+///     ));
+/// }
+/// ```
+#[derive(Component)]
+pub struct SpawnPoint<T>
+where
+    T: Send + Sync + 'static,
+{
+    /// The objects that can be spawned here. `None` means all objects.
+    pub matcher: Option<Matcher<T>>,
+}
+
+impl<T> Default for SpawnPoint<T>
+where
+    T: Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self { matcher: None }
+    }
+}
+
+impl<T> SpawnPoint<T>
+where
+    T: Send + Sync + 'static,
+{
+    /// Create a spawn point for all objects matching a pattern created with [`spew::matches!`](crate::matches).
+    pub fn only(matcher: Matcher<T>) -> Self {
+        Self {
+            matcher: Some(matcher),
+        }
+    }
+
+    /// Whether the object can be spawned here.
+    pub fn accepts(&self, object: &T) -> bool {
+        self.matcher
+            .as_ref()
+            .is_none_or(|matcher| matcher.matches(object))
+    }
+}
+
+/// How to choose one of the [`SpawnPoint`]s that accept an object. See [`SpawnEvent::at_spawn_point`](crate::prelude::SpawnEvent::at_spawn_point).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpawnPointSelector {
+    /// Pick a random spawn point with [`SpewRng`].
+    Random,
+    /// Use the spawn points one after another.
+    RoundRobin,
+    /// Pick the spawn point that is closest to the given entity.
+    NearestTo(Entity),
+    /// Pick the spawn point whose distance to the closest of the given entities, e.g. the players, is the largest.
+    FarthestFrom(Vec<Entity>),
+}
+
+/// Remembers which spawn point [`SpawnPointSelector::RoundRobin`] uses next.
+#[derive(Resource)]
+pub(crate) struct RoundRobinCursor<T>
+where
+    T: Send + Sync + 'static,
+{
+    next: usize,
+    _spawner_enum_type: std::marker::PhantomData<T>,
+}
+
+impl<T> Default for RoundRobinCursor<T>
+where
+    T: Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self {
+            next: 0,
+            _spawner_enum_type: std::marker::PhantomData,
+        }
+    }
+}
+
+/// Find the transform of the spawn point the selector picks for the object.
+pub(crate) fn resolve_spawn_point<T>(
+    world: &mut World,
+    selector: &SpawnPointSelector,
+    object: &T,
+) -> Result<GlobalTransform, SpawnPointError>
+where
+    T: Debug + Send + Sync + 'static,
+{
+    let mut spawn_points = world.query::<(Entity, &SpawnPoint<T>, &GlobalTransform)>();
+    let mut candidates: Vec<_> = spawn_points
+        .iter(world)
+        .filter(|(_, spawn_point, _)| spawn_point.accepts(object))
+        .map(|(entity, _, transform)| (entity, *transform))
+        .collect();
+    if candidates.is_empty() {
+        return Err(SpawnPointError::NoSpawnPoint {
+            object: format!("{object:?}"),
+        });
+    }
+    // Queries don't guarantee any order, so sort the spawn points to make the selection reproducible.
+    candidates.sort_unstable_by_key(|(entity, _)| *entity);
+    let index = match selector {
+        SpawnPointSelector::Random => {
+            world.resource_mut::<SpewRng>().next_u64() as usize % candidates.len()
+        }
+        SpawnPointSelector::RoundRobin => {
+            let mut cursor = world.resource_mut::<RoundRobinCursor<T>>();
+            let index = cursor.next % candidates.len();
+            cursor.next = index + 1;
+            index
+        }
+        SpawnPointSelector::NearestTo(target) => {
+            let Some(target) = world.get::<GlobalTransform>(*target) else {
+                return Err(SpawnPointError::MissingTarget {
+                    object: format!("{object:?}"),
+                    target: *target,
+                });
+            };
+            let target = target.translation();
+            best_index(&candidates, |translation| {
+                -translation.distance_squared(target)
+            })
+        }
+        SpawnPointSelector::FarthestFrom(targets) => {
+            let targets: Vec<_> = targets
+                .iter()
+                .filter_map(|&target| world.get::<GlobalTransform>(target))
+                .map(GlobalTransform::translation)
+                .collect();
+            best_index(&candidates, |translation| {
+                targets
+                    .iter()
+                    .map(|&target| translation.distance_squared(target))
+                    .fold(f32::INFINITY, f32::min)
+            })
+        }
+    };
+    Ok(candidates[index].1)
+}
+
+/// The index of the candidate whose translation has the highest score. Ties go to the first candidate.
+fn best_index(candidates: &[(Entity, GlobalTransform)], score: impl Fn(Vec3) -> f32) -> usize {
+    let mut best = (0, f32::NEG_INFINITY);
+    for (index, (_, transform)) in candidates.iter().enumerate() {
+        let candidate_score = score(transform.translation());
+        if candidate_score > best.1 {
+            best = (index, candidate_score);
+        }
+    }
+    best.0
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use bevy::prelude::*;
+
+    #[derive(Debug, Eq, PartialEq, Clone)]
+    enum Object {
+        WithContext,
+        WithoutContext,
+        Matched,
+        Pooled,
+    }
+
+    #[derive(Component)]
+    struct Spawned;
+
+    fn spawn_with_context(In(context): In<SpawnContext<Object, ()>>, mut commands: Commands) {
+        let transform = context.spawn_point.unwrap_or_default();
+        commands.spawn((Spawned, transform));
+    }
+
+    fn spawn_without_context(mut commands: Commands) {
+        commands.spawn((Spawned, GlobalTransform::default()));
+    }
+
+    fn spawn_matched(In((_object, ())): In<(Object, ())>, mut commands: Commands) {
+        commands.spawn((Spawned, GlobalTransform::default()));
+    }
+
+    fn reset(In((_entity, ())): In<(Entity, ())>) {}
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(SpewPlugin::<Object>::default())
+            .add_spawners((
+                (Object::WithContext, with_context(spawn_with_context)),
+                (Object::WithoutContext, spawn_without_context),
+                (crate::matches!(Object::Matched), spawn_matched),
+                (Object::Pooled, with_context(spawn_with_context)),
+            ))
+            .add_pool(Object::Pooled, reset, PoolConfig::default());
+        app.world_mut().spawn((
+            SpawnPoint::<Object>::default(),
+            GlobalTransform::from_xyz(1.0, 2.0, 3.0),
+        ));
+        app
+    }
+
+    /// Spawn the object at a spawn point and return where it was spawned, or the failure if it failed.
+    fn spawn(
+        app: &mut App,
+        object: Object,
+        selector: SpawnPointSelector,
+    ) -> Result<Vec3, SpawnFailed<Object>> {
+        app.world_mut()
+            .send_event(SpawnEvent::<Object>::new(object).at_spawn_point(selector));
+        app.update();
+
+        let mut failed = app
+            .world_mut()
+            .resource_mut::<Events<SpawnFailed<Object>>>();
+        if let Some(failed) = failed.drain().next() {
+            return Err(failed);
+        }
+        let world = app.world_mut();
+        let (entity, transform) = world
+            .query_filtered::<(Entity, &GlobalTransform), With<Spawned>>()
+            .single(world);
+        let translation = transform.translation();
+        world.despawn(entity);
+        Ok(translation)
+    }
+
+    #[test]
+    fn spawner_with_context_receives_spawn_point() {
+        assert_eq!(
+            spawn(&mut app(), Object::WithContext, SpawnPointSelector::Random).ok(),
+            Some(Vec3::new(1.0, 2.0, 3.0))
+        );
+    }
+
+    #[test]
+    fn spawners_that_cant_receive_spawn_point_fail() {
+        for object in [Object::WithoutContext, Object::Matched, Object::Pooled] {
+            let error = spawn(&mut app(), object, SpawnPointSelector::Random)
+                .unwrap_err()
+                .error
+                .to_string();
+            assert!(error.contains("was requested at a spawn point"), "{error}");
+        }
+    }
+
+    #[test]
+    fn fail_without_nearest_to_target() {
+        let mut app = app();
+        let missing = app.world_mut().spawn_empty().id();
+        app.world_mut().despawn(missing);
+        let without_transform = app.world_mut().spawn_empty().id();
+
+        for target in [missing, without_transform] {
+            let failed = spawn(
+                &mut app,
+                Object::WithContext,
+                SpawnPointSelector::NearestTo(target),
+            )
+            .unwrap_err();
+            assert_eq!(
+                failed.error.downcast_ref::<SpawnPointError>(),
+                Some(&SpawnPointError::MissingTarget {
+                    object: "WithContext".to_string(),
+                    target,
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn fail_without_accepting_spawn_point() {
+        let mut app = app();
+        app.world_mut().clear_entities();
+
+        let failed = spawn(&mut app, Object::WithContext, SpawnPointSelector::Random).unwrap_err();
+        assert_eq!(
+            failed.error.downcast_ref::<SpawnPointError>(),
+            Some(&SpawnPointError::NoSpawnPoint {
+                object: "WithContext".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn failed_spawns_dont_pick_a_spawn_point() {
+        let mut app = app();
+        app.world_mut().spawn((
+            SpawnPoint::<Object>::default(),
+            GlobalTransform::from_xyz(4.0, 5.0, 6.0),
+        ));
+
+        for selector in [SpawnPointSelector::Random, SpawnPointSelector::RoundRobin] {
+            assert!(spawn(&mut app, Object::Pooled, selector).is_err());
+        }
+        assert_eq!(app.world().resource::<SpewRng>(), &SpewRng::default());
+        assert_eq!(
+            spawn(
+                &mut app,
+                Object::WithContext,
+                SpawnPointSelector::RoundRobin
+            )
+            .ok(),
+            Some(Vec3::new(1.0, 2.0, 3.0))
+        );
+    }
+}
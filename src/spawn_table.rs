@@ -0,0 +1,391 @@
+use crate::budget::SpawnBacklog;
+use crate::events::{delay_spawn_events, Delay, SpawnEvent, SpawnHandle};
+use crate::pending::PendingSpawns;
+use crate::plugin::SpewSystemSet;
+use bevy::asset::io::Reader;
+use bevy::asset::{
+    ron, Asset, AssetLoader, AssetServer, AsyncReadExt, LoadContext, LoadState, UntypedAssetId,
+    VisitAssetDependencies,
+};
+use bevy::ecs::schedule::InternedScheduleLabel;
+use bevy::prelude::*;
+use bevy::reflect::utility::GenericTypePathCell;
+use bevy::reflect::TypePath;
+use serde::de::{DeserializeOwned, Error as _};
+use serde::{Deserialize, Deserializer};
+use std::any::type_name;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+/// A list of objects to spawn, loaded from a `.spawn.ron` file so that it can be tuned without recompiling.
+/// Play it by sending a [`PlaySpawnTable`] event, which sends a [`SpawnEvent`] for every entry.
+/// Enable spawn tables for a [`SpewPlugin`](crate::prelude::SpewPlugin) with [`SpewPlugin::spawn_tables`](crate::prelude::SpewPlugin::spawn_tables).
+///
+/// The file contains the entries of the table. The delay is optional and can be any [`Delay`], with seconds written as a number:
+/// ```ron
+/// (
+///     entries: [
+///         (object: Goblin, data: 1.0),
+///         (object: Goblin, data: 2.0, delay: Seconds(1.5)),
+///         (object: Dragon, data: 0.0, delay: Frames(60)),
+///     ],
+/// )
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(bound(deserialize = "T: DeserializeOwned, D: DeserializeOwned"))]
+pub struct SpawnTable<T, D = ()> {
+    /// The objects to spawn, in the order they are requested in.
+    pub entries: Vec<SpawnTableEntry<T, D>>,
+}
+
+/// A single object in a [`SpawnTable`].
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(bound(deserialize = "T: DeserializeOwned, D: DeserializeOwned"))]
+pub struct SpawnTableEntry<T, D = ()> {
+    /// The object to spawn.
+    pub object: T,
+    /// The user-provided data to pass to the spawner.
+    pub data: D,
+    /// The delay to apply, counted from the moment the table is played.
+    #[serde(default)]
+    pub delay: Delay,
+}
+
+impl<T, D> Asset for SpawnTable<T, D>
+where
+    T: Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+}
+
+impl<T, D> VisitAssetDependencies for SpawnTable<T, D> {
+    fn visit_dependencies(&self, _visit: &mut impl FnMut(UntypedAssetId)) {}
+}
+
+impl<T, D> TypePath for SpawnTable<T, D>
+where
+    T: 'static,
+    D: 'static,
+{
+    fn type_path() -> &'static str {
+        static CELL: GenericTypePathCell = GenericTypePathCell::new();
+        CELL.get_or_insert::<Self, _>(|| {
+            format!(
+                "spew::SpawnTable<{}, {}>",
+                type_name::<T>(),
+                type_name::<D>()
+            )
+        })
+    }
+
+    fn short_type_path() -> &'static str {
+        static CELL: GenericTypePathCell = GenericTypePathCell::new();
+        CELL.get_or_insert::<Self, _>(|| {
+            format!("SpawnTable<{}, {}>", type_name::<T>(), type_name::<D>())
+        })
+    }
+
+    fn type_ident() -> Option<&'static str> {
+        Some("SpawnTable")
+    }
+
+    fn crate_name() -> Option<&'static str> {
+        Some("spew")
+    }
+
+    fn module_path() -> Option<&'static str> {
+        Some("spew")
+    }
+}
+
+/// The way a [`Delay`] is written in a spawn table.
+#[derive(Deserialize)]
+enum SerializedDelay {
+    Frames(usize),
+    Seconds(f32),
+    AtFrame(u32),
+    AtElapsed(f32),
+}
+
+impl<'de> Deserialize<'de> for Delay {
+    fn deserialize<De: Deserializer<'de>>(deserializer: De) -> Result<Self, De::Error> {
+        // Negative, infinite or NaN seconds can't be represented as a `Duration`.
+        let duration = |seconds| Duration::try_from_secs_f32(seconds).map_err(De::Error::custom);
+        Ok(match SerializedDelay::deserialize(deserializer)? {
+            SerializedDelay::Frames(frames) => Delay::Frames(frames),
+            SerializedDelay::Seconds(seconds) => Delay::Seconds(duration(seconds)?),
+            SerializedDelay::AtFrame(frame) => Delay::AtFrame(frame),
+            SerializedDelay::AtElapsed(seconds) => Delay::AtElapsed(duration(seconds)?),
+        })
+    }
+}
+
+/// An event that plays a [`SpawnTable`] by sending a [`SpawnEvent`] for each of its entries.
+/// If the table is still loading, it is played as soon as it is loaded.
+///
+/// When the file of a table that is still playing changes, the entries that have not been spawned yet are cancelled
+/// and the new version of the table is played from the start. This requires bevy's `file_watcher` feature.
+///
+/// # Example
+/// ```rust,ignore
+/// use spew::prelude::*;
+/// use bevy::prelude::*;
+///
+/// #[derive(Debug, Eq, PartialEq, Clone, serde::Deserialize)]
+/// enum Enemy {
+///     Goblin,
+///     Dragon,
+/// }
+///
+/// fn start_wave(asset_server: Res<AssetServer>, mut play_events: EventWriter<PlaySpawnTable<Enemy, f32>>) {
+///     play_events.send(PlaySpawnTable(asset_server.load("waves/first.spawn.ron")));
+/// }
+/// ```
+#[derive(Event)]
+pub struct PlaySpawnTable<T, D = ()>(pub Handle<SpawnTable<T, D>>)
+where
+    T: Send + Sync + 'static,
+    D: Send + Sync + 'static;
+
+/// Loads [`SpawnTable`]s from `.spawn.ron` files.
+pub(crate) struct SpawnTableLoader<T, D>(std::marker::PhantomData<fn() -> (T, D)>);
+
+impl<T, D> Default for SpawnTableLoader<T, D> {
+    fn default() -> Self {
+        Self(std::marker::PhantomData)
+    }
+}
+
+impl<T, D> AssetLoader for SpawnTableLoader<T, D>
+where
+    T: DeserializeOwned + Send + Sync + 'static,
+    D: DeserializeOwned + Send + Sync + 'static,
+{
+    type Asset = SpawnTable<T, D>;
+    type Settings = ();
+    type Error = SpawnTableLoaderError;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["spawn.ron"]
+    }
+}
+
+/// An error that occurred while loading a [`SpawnTable`].
+#[derive(Debug)]
+pub(crate) enum SpawnTableLoaderError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+}
+
+impl Display for SpawnTableLoaderError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(error) => write!(f, "Failed to read the spawn table: {error}"),
+            Self::Ron(error) => write!(f, "Failed to parse the spawn table: {error}"),
+        }
+    }
+}
+
+impl Error for SpawnTableLoaderError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(error) => Some(error),
+            Self::Ron(error) => Some(error),
+        }
+    }
+}
+
+impl From<std::io::Error> for SpawnTableLoaderError {
+    fn from(error: std::io::Error) -> Self {
+        Self::Io(error)
+    }
+}
+
+impl From<ron::error::SpannedError> for SpawnTableLoaderError {
+    fn from(error: ron::error::SpannedError) -> Self {
+        Self::Ron(error)
+    }
+}
+
+/// The spawn tables that were played and still have entries waiting to be spawned.
+#[derive(Resource)]
+pub(crate) struct SpawnTablePlays<T, D>
+where
+    T: Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    plays: Vec<SpawnTablePlay<T, D>>,
+}
+
+struct SpawnTablePlay<T, D>
+where
+    T: Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    table: Handle<SpawnTable<T, D>>,
+    /// The handles of the spawn events sent for the table, or `None` if it is waiting to be loaded.
+    spawns: Option<Vec<SpawnHandle>>,
+}
+
+impl<T, D> Default for SpawnTablePlays<T, D>
+where
+    T: Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self { plays: Vec::new() }
+    }
+}
+
+/// Register everything needed to play spawn tables. Called by [`SpewPlugin::spawn_tables`](crate::prelude::SpewPlugin::spawn_tables).
+pub(crate) fn add_spawn_tables<T, D>(app: &mut App, schedule: InternedScheduleLabel)
+where
+    T: Clone + Eq + DeserializeOwned + Send + Sync + 'static,
+    D: Clone + DeserializeOwned + Send + Sync + 'static,
+{
+    app.init_asset::<SpawnTable<T, D>>()
+        .init_asset_loader::<SpawnTableLoader<T, D>>()
+        .add_event::<PlaySpawnTable<T, D>>()
+        .init_resource::<SpawnTablePlays<T, D>>()
+        .add_systems(
+            schedule,
+            play_spawn_tables::<T, D>
+                .before(delay_spawn_events::<T, D>)
+                .in_set(SpewSystemSet),
+        );
+}
+
+fn play_spawn_tables<T, D>(
+    mut play_events: EventReader<PlaySpawnTable<T, D>>,
+    mut asset_events: EventReader<AssetEvent<SpawnTable<T, D>>>,
+    tables: Res<Assets<SpawnTable<T, D>>>,
+    asset_server: Res<AssetServer>,
+    mut plays: ResMut<SpawnTablePlays<T, D>>,
+    mut pending_spawns: ResMut<PendingSpawns<T, D>>,
+    mut backlog: ResMut<SpawnBacklog<T, D>>,
+    mut spawn_events: EventWriter<SpawnEvent<T, D>>,
+) where
+    T: Clone + Eq + Send + Sync + 'static,
+    D: Clone + Send + Sync + 'static,
+{
+    plays.plays.extend(
+        play_events
+            .read()
+            .map(|PlaySpawnTable(table)| SpawnTablePlay {
+                table: table.clone(),
+                spawns: None,
+            }),
+    );
+    for event in asset_events.read() {
+        let AssetEvent::Modified { id } = event else {
+            continue;
+        };
+        for play in plays.plays.iter_mut().filter(|play| play.table.id() == *id) {
+            for handle in play.spawns.take().into_iter().flatten() {
+                pending_spawns.cancel(handle);
+                backlog.cancel(handle);
+            }
+        }
+    }
+
+    plays.plays.retain_mut(|play| {
+        if let Some(spawns) = &play.spawns {
+            return spawns
+                .iter()
+                .any(|&handle| pending_spawns.contains(handle) || backlog.contains(handle));
+        }
+        let Some(table) = tables.get(&play.table) else {
+            if let LoadState::Failed(error) = asset_server.load_state(&play.table) {
+                warn!("Did not play a spawn table because it failed to load: {error}");
+                return false;
+            }
+            return true;
+        };
+        let events: Vec<_> = table
+            .entries
+            .iter()
+            .map(|entry| SpawnEvent {
+                delay: entry.delay,
+                ..SpawnEvent::with_data(entry.object.clone(), entry.data.clone())
+            })
+            .collect();
+        play.spawns = Some(events.iter().map(|event| event.handle).collect());
+        spawn_events.send_batch(events);
+        true
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_delay() {
+        assert_eq!(
+            ron::from_str::<Delay>("Seconds(1.5)").unwrap(),
+            Delay::Seconds(Duration::from_millis(1500))
+        );
+        assert_eq!(
+            ron::from_str::<Delay>("AtFrame(3)").unwrap(),
+            Delay::AtFrame(3)
+        );
+        assert!(ron::from_str::<Delay>("Seconds(-1.0)").is_err());
+        assert!(ron::from_str::<Delay>("AtElapsed(1e40)").is_err());
+    }
+
+    #[test]
+    fn reload_cancels_waiting_spawns() {
+        use crate::prelude::*;
+        use crate::test_support::{record, update, Spawned};
+
+        #[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+        enum Object {
+            Marker,
+        }
+
+        let table = |ids: &[u32]| SpawnTable {
+            entries: ids
+                .iter()
+                .map(|&id| SpawnTableEntry {
+                    object: Object::Marker,
+                    data: id,
+                    delay: Delay::default(),
+                })
+                .collect(),
+        };
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()))
+            .init_resource::<Spawned>()
+            .add_plugins(
+                SpewPlugin::<Object, u32>::default()
+                    .spawn_budget(SpawnBudget::Spawns(1))
+                    .spawn_tables(),
+            )
+            .add_spawner((Object::Marker, record));
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<SpawnTable<Object, u32>>>()
+            .add(table(&[1, 2, 3, 4]));
+        app.world_mut().send_event(PlaySpawnTable(handle.clone()));
+        assert_eq!(update(&mut app), vec![1]);
+
+        // Change the table while its entries are still waiting for the spawn budget, as if the file was edited
+        app.world_mut()
+            .resource_mut::<Assets<SpawnTable<Object, u32>>>()
+            .insert(&handle, table(&[10, 20]));
+        let spawned: Vec<_> = (0..5).flat_map(|_| update(&mut app)).collect();
+        assert_eq!(spawned, vec![2, 10, 20]);
+    }
+}
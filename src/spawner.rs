@@ -1,9 +1,18 @@
-use crate::events::{delay_spawn_events, ReadySpawnEvent};
-use crate::plugin::SpewSystemSet;
-use bevy::ecs::system::SystemState;
+use crate::budget::spend_budget;
+use crate::despawner::Spewed;
+use crate::error::{MissingPluginError, SpawnError};
+use crate::events::{ReadySpawnEvent, SpawnBatchEvent, SpawnFailed, SpawnRetries, SpawnedEvent};
+use crate::plugin::{SpewConfig, UnhandledSpawnPolicy};
+use crate::pool::{self, PoolAction, SpawnPools};
+use crate::spawn_point::{resolve_spawn_point, SpawnPointSelector};
+use crate::system::CachedSystem;
+use crate::tracking::SpawnTracker;
+use bevy::core::FrameCount;
 use bevy::prelude::*;
-use bevy::utils::all_tuples;
+use bevy::utils::{all_tuples, HashMap, HashSet};
+use std::any::type_name;
 use std::fmt::Debug;
+use std::mem::{self, Discriminant};
 
 /// Abstraction over a tuple of [`Spawner`]s.
 /// See [`SpewApp::add_spawners`](crate::prelude::SpewApp::add_spawners) for more information.
@@ -19,48 +28,979 @@ pub trait Spawner<Marker>: Send + Sync + 'static {
     fn add_to_app(self, app: &mut App);
 }
 
+/// The return type of a spawning function.
+/// Spawning functions may return nothing, a single [`Entity`] or multiple entities.
+/// The returned entities are reported in the [`SpawnedEvent`](crate::prelude::SpawnedEvent) sent after spawning.
+///
+/// Spawning functions can also fail by returning a `Result` of any of these with an error that converts into a [`SpawnError`].
+/// Failures are reported in a [`SpawnFailed`](crate::prelude::SpawnFailed) event instead of a `SpawnedEvent`
+/// and can be retried with [`SpewPlugin::failed_spawns`](crate::prelude::SpewPlugin::failed_spawns).
+/// A failing spawner should not leave any entities behind.
+pub trait SpawnerOutput: Send + Sync + 'static {
+    /// Convert the output into the list of spawned entities. Called internally.
+    fn into_entities(self) -> Vec<Entity>;
+
+    /// Convert the output into the list of spawned entities or the reason why spawning failed. Called internally.
+    fn into_result(self) -> Result<Vec<Entity>, SpawnError>
+    where
+        Self: Sized,
+    {
+        Ok(self.into_entities())
+    }
+}
+
+impl SpawnerOutput for () {
+    fn into_entities(self) -> Vec<Entity> {
+        Vec::new()
+    }
+}
+
+impl SpawnerOutput for Entity {
+    fn into_entities(self) -> Vec<Entity> {
+        vec![self]
+    }
+}
+
+impl SpawnerOutput for Option<Entity> {
+    fn into_entities(self) -> Vec<Entity> {
+        self.into_iter().collect()
+    }
+}
+
+impl SpawnerOutput for Vec<Entity> {
+    fn into_entities(self) -> Vec<Entity> {
+        self
+    }
+}
+
+impl<O, E> SpawnerOutput for Result<O, E>
+where
+    O: SpawnerOutput,
+    E: Into<SpawnError> + Send + Sync + 'static,
+{
+    fn into_entities(self) -> Vec<Entity> {
+        self.map(O::into_entities).unwrap_or_default()
+    }
+
+    fn into_result(self) -> Result<Vec<Entity>, SpawnError> {
+        self.map(O::into_entities).map_err(Into::into)
+    }
+}
+
 impl<T, F, Marker> Spawner<Marker> for (T, F)
 where
     T: Debug + Eq + Send + Sync + 'static,
     F: SystemParamFunction<Marker>,
     Marker: Send + Sync + 'static,
     F::In: Send + Sync + 'static,
+    F::Out: SpawnerOutput,
 {
     fn add_to_app(self, app: &mut App) {
-        let (object, mut spawn_function) = self;
-        let system = move |world: &mut World| {
-            let mut events = world
-                .get_resource_mut::<Events<ReadySpawnEvent<T, F::In>>>()
-                .unwrap();
-            let mut handled_events = Vec::new();
-            let mut unhandled_events = Vec::new();
-
-            for event in events.drain() {
-                if event.object == object {
-                    handled_events.push(event);
-                } else {
-                    unhandled_events.push(event);
-                }
+        let (object, spawn_function) = self;
+        let mut spawn_function = CachedSystem::new(spawn_function);
+        let spawn = move |world: &mut World, request: SpawnRequest<T>, user_data: F::In| {
+            if request.spawn_point.is_some() {
+                return Err(spawn_point_unsupported(request.object));
+            }
+            spawn_function.run(world, user_data).into_result()
+        };
+        let Some(mut registry) = app
+            .world_mut()
+            .get_resource_mut::<SpawnerRegistry<T, F::In>>()
+        else {
+            panic!("{}", MissingPluginError::new::<T, F::In>(&object));
+        };
+        registry.insert(object, Box::new(spawn));
+    }
+}
+
+/// A pattern that selects which objects a spawner handles, created with [`spew::matches!`](crate::matches).
+/// Spawners registered with a matcher receive the object that matched together with the user data in an `In<(T, D)>` parameter,
+/// so that a single spawner can handle all variants with a payload, e.g. every level of `Enemy::Goblin { level: u8 }`.
+///
+/// Spawners registered for an exact object take precedence over matchers.
+/// Of multiple matching matchers, the one registered first is used.
+///
+/// # Example
+/// ```rust
+/// use spew::prelude::*;
+/// use bevy::prelude::*;
+///
+/// #[derive(Debug, Eq, PartialEq, Clone)]
+/// enum Enemy {
+///    Goblin { level: u8 },
+///    Dragon,
+/// }
+///
+/// App::new()
+///     .add_plugins(SpewPlugin::<Enemy, Transform>::default())
+///     // Register the more specific matcher first, since the first matching matcher is used
+///     .add_spawner((spew::matches!(Enemy::Goblin { level } if *level > 10), spawn_goblin_boss))
+///     .add_spawner((spew::matches!(Enemy::Goblin { .. }), spawn_goblin));
+///
+/// fn spawn_goblin(In((enemy, transform)): In<(Enemy, Transform)>, mut commands: Commands) {
+///     info!("Spawning {enemy:?}");
+///     commands.spawn((Name::new("Goblin"), transform));
+/// }
+///
+/// fn spawn_goblin_boss(In((enemy, transform)): In<(Enemy, Transform)>, mut commands: Commands) {
+///     info!("Spawning the boss {enemy:?}");
+///     commands.spawn((Name::new("Goblin Boss"), transform));
+/// }
+/// ```
+pub struct Matcher<T> {
+    predicate: fn(&T) -> bool,
+    pattern: &'static str,
+}
+
+impl<T> Matcher<T> {
+    /// Create a matcher from a predicate. The pattern is only used to describe the matcher in logs.
+    /// Usually, you want to use [`spew::matches!`](crate::matches) instead.
+    pub fn new(pattern: &'static str, predicate: fn(&T) -> bool) -> Self {
+        Self { predicate, pattern }
+    }
+
+    /// Whether the object matches.
+    pub fn matches(&self, object: &T) -> bool {
+        (self.predicate)(object)
+    }
+
+    /// The pattern the matcher was created from.
+    pub fn pattern(&self) -> &'static str {
+        self.pattern
+    }
+}
+
+impl<T> Debug for Matcher<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Matcher")
+            .field("pattern", &self.pattern)
+            .finish()
+    }
+}
+
+impl<T> Clone for Matcher<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Matcher<T> {}
+
+/// Marks spawners registered with a [`Matcher`] so that they don't overlap with spawners registered for an exact object.
+#[doc(hidden)]
+pub struct MatcherMarker<D, Marker>(std::marker::PhantomData<(D, Marker)>);
+
+impl<T, D, F, Marker> Spawner<MatcherMarker<D, Marker>> for (Matcher<T>, F)
+where
+    T: Clone + Debug + Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+    F: SystemParamFunction<Marker, In = (T, D)>,
+    Marker: Send + Sync + 'static,
+    F::Out: SpawnerOutput,
+{
+    fn add_to_app(self, app: &mut App) {
+        let (matcher, spawn_function) = self;
+        let mut spawn_function = CachedSystem::new(spawn_function);
+        let spawn = move |world: &mut World, request: SpawnRequest<T>, user_data: D| {
+            if request.spawn_point.is_some() {
+                return Err(spawn_point_unsupported(request.object));
+            }
+            spawn_function
+                .run(world, (request.object.clone(), user_data))
+                .into_result()
+        };
+        let Some(mut registry) = app.world_mut().get_resource_mut::<SpawnerRegistry<T, D>>() else {
+            panic!(
+                "{}",
+                MissingPluginError::described::<T, D>(format!(
+                    "objects matching `{}`",
+                    matcher.pattern
+                ))
+            );
+        };
+        registry.matchers.push((matcher, Box::new(spawn)));
+    }
+}
+
+/// A spawner wrapped with [`with_context`], which receives a [`SpawnContext`] instead of only the user data.
+pub struct WithContext<F>(F);
+
+/// Let a spawner receive a [`SpawnContext`] with the requested object and everything else known about the request in an `In<SpawnContext<T, D>>` parameter.
+/// This is useful when one function handles multiple objects, either by registering it for each of them or with a [`Matcher`].
+///
+/// # Example
+/// ```rust
+/// use spew::prelude::*;
+/// use bevy::prelude::*;
+///
+/// #[derive(Debug, Eq, PartialEq, Clone)]
+/// enum Furniture {
+///    Chair,
+///    Table,
+/// }
+///
+/// App::new()
+///     .add_plugins(SpewPlugin::<Furniture, Transform>::default())
+///     .add_spawners((
+///         (Furniture::Chair, with_context(spawn_furniture)),
+///         (Furniture::Table, with_context(spawn_furniture)),
+///     ));
+///
+/// fn spawn_furniture(In(context): In<SpawnContext<Furniture, Transform>>, mut commands: Commands) {
+///     info!("Spawning {:?} requested in frame {}", context.object, context.frame);
+///     commands.spawn((Name::new(format!("{:?}", context.object)), context.data));
+/// }
+/// ```
+pub fn with_context<F>(spawn_function: F) -> WithContext<F> {
+    WithContext(spawn_function)
+}
+
+/// Everything known about a spawn request, passed to spawners wrapped with [`with_context`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpawnContext<T, D> {
+    /// The object that was requested.
+    pub object: T,
+    /// The user-provided data.
+    pub data: D,
+    /// The [`FrameCount`](bevy::core::FrameCount) of the frame the spawn was requested in.
+    /// Repetitions of a [`SpawnEvent`](crate::prelude::SpawnEvent) report the frame of the original request.
+    pub frame: u32,
+    /// The id passed to [`SpawnEvent::correlation_id`](crate::prelude::SpawnEvent::correlation_id).
+    pub correlation_id: Option<u64>,
+    /// The parent passed to [`SpawnEvent::with_parent`](crate::prelude::SpawnEvent::with_parent).
+    /// Spew attaches the root entities to it after the spawner ran.
+    pub parent: Option<Entity>,
+    /// The transform of the [`SpawnPoint`](crate::prelude::SpawnPoint) picked for the request with [`SpawnEvent::at_spawn_point`](crate::prelude::SpawnEvent::at_spawn_point).
+    pub spawn_point: Option<GlobalTransform>,
+}
+
+/// Marks spawners wrapped with [`with_context`] so that they don't overlap with other spawners.
+#[doc(hidden)]
+pub struct ContextMarker<D, Marker>(std::marker::PhantomData<(D, Marker)>);
+
+impl<T, D, F, Marker> Spawner<ContextMarker<D, Marker>> for (T, WithContext<F>)
+where
+    T: Clone + Debug + Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+    F: SystemParamFunction<Marker, In = SpawnContext<T, D>>,
+    Marker: Send + Sync + 'static,
+    F::Out: SpawnerOutput,
+{
+    fn add_to_app(self, app: &mut App) {
+        let (object, WithContext(spawn_function)) = self;
+        let Some(mut registry) = app.world_mut().get_resource_mut::<SpawnerRegistry<T, D>>() else {
+            panic!("{}", MissingPluginError::new::<T, D>(&object));
+        };
+        registry.insert(object, context_spawn_function(spawn_function));
+    }
+}
+
+impl<T, D, F, Marker> Spawner<ContextMarker<D, Marker>> for (Matcher<T>, WithContext<F>)
+where
+    T: Clone + Debug + Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+    F: SystemParamFunction<Marker, In = SpawnContext<T, D>>,
+    Marker: Send + Sync + 'static,
+    F::Out: SpawnerOutput,
+{
+    fn add_to_app(self, app: &mut App) {
+        let (matcher, WithContext(spawn_function)) = self;
+        let Some(mut registry) = app.world_mut().get_resource_mut::<SpawnerRegistry<T, D>>() else {
+            panic!(
+                "{}",
+                MissingPluginError::described::<T, D>(format!(
+                    "objects matching `{}`",
+                    matcher.pattern
+                ))
+            );
+        };
+        registry
+            .matchers
+            .push((matcher, context_spawn_function(spawn_function)));
+    }
+}
+
+fn context_spawn_function<T, D, F, Marker>(spawn_function: F) -> BoxedSpawnFunction<T, D>
+where
+    T: Clone + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+    F: SystemParamFunction<Marker, In = SpawnContext<T, D>>,
+    Marker: Send + Sync + 'static,
+    F::Out: SpawnerOutput,
+{
+    let mut spawn_function = CachedSystem::new(spawn_function);
+    Box::new(
+        move |world: &mut World, request: SpawnRequest<T>, user_data: D| {
+            let context = SpawnContext {
+                object: request.object.clone(),
+                data: user_data,
+                frame: request.frame,
+                correlation_id: request.correlation_id,
+                parent: request.parent,
+                spawn_point: request.spawn_point,
+            };
+            spawn_function.run(world, context).into_result()
+        },
+    )
+}
+
+/// A spawner wrapped with [`batch`], which receives the data of a whole [`SpawnBatchEvent`] at once.
+pub struct Batch<F>(F);
+
+/// Register a spawner for [`SpawnBatchEvent`]s, which receives the data of all instances in an `In<Vec<D>>` parameter.
+/// This lets the spawner use [`Commands::spawn_batch`] and avoids running it once per instance.
+/// Batch spawners are only used for [`SpawnBatchEvent`]s and are registered in addition to the regular spawner of the object,
+/// which still handles [`SpawnEvent`](crate::prelude::SpawnEvent)s and batches of objects without a batch spawner.
+///
+/// # Example
+/// ```rust
+/// use spew::prelude::*;
+/// use bevy::prelude::*;
+///
+/// #[derive(Debug, Eq, PartialEq)]
+/// enum Object {
+///    Tree
+/// }
+///
+/// App::new()
+///     .add_plugins(SpewPlugin::<Object, Transform>::default())
+///     .add_spawner((Object::Tree, spawn_tree))
+///     .add_spawner((Object::Tree, batch(spawn_trees)));
+///
+/// fn spawn_tree(In(transform): In<Transform>, mut commands: Commands) {
+///     commands.spawn((Name::new("Tree"), transform));
+/// }
+///
+/// fn spawn_trees(In(transforms): In<Vec<Transform>>, mut commands: Commands) {
+///     commands.spawn_batch(transforms.into_iter().map(|transform| (Name::new("Tree"), transform)));
+/// }
+/// ```
+pub fn batch<F>(spawn_function: F) -> Batch<F> {
+    Batch(spawn_function)
+}
+
+/// Marks spawners wrapped with [`batch`] so that they don't overlap with other spawners.
+#[doc(hidden)]
+pub struct BatchMarker<D, Marker>(std::marker::PhantomData<(D, Marker)>);
+
+impl<T, D, F, Marker> Spawner<BatchMarker<D, Marker>> for (T, Batch<F>)
+where
+    T: Debug + Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+    F: SystemParamFunction<Marker, In = Vec<D>>,
+    Marker: Send + Sync + 'static,
+    F::Out: SpawnerOutput,
+{
+    fn add_to_app(self, app: &mut App) {
+        let (object, Batch(spawn_function)) = self;
+        let mut spawn_function = CachedSystem::new(spawn_function);
+        let spawn = move |world: &mut World, _request: SpawnRequest<T>, user_data: Vec<D>| {
+            spawn_function.run(world, user_data).into_result()
+        };
+        let Some(mut registry) = app.world_mut().get_resource_mut::<SpawnerRegistry<T, D>>() else {
+            panic!("{}", MissingPluginError::new::<T, D>(&object));
+        };
+        registry
+            .batch_spawners
+            .entry(mem::discriminant(&object))
+            .or_default()
+            .push((object, Box::new(spawn)));
+    }
+}
+
+/// What a boxed spawn function knows about the request besides the user data.
+pub(crate) struct SpawnRequest<'a, T> {
+    pub(crate) object: &'a T,
+    pub(crate) frame: u32,
+    pub(crate) correlation_id: Option<u64>,
+    pub(crate) parent: Option<Entity>,
+    pub(crate) spawn_point: Option<GlobalTransform>,
+}
+
+impl<T> Clone for SpawnRequest<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for SpawnRequest<'_, T> {}
+
+type BoxedSpawnFunction<T, D> =
+    Box<dyn FnMut(&mut World, SpawnRequest<T>, D) -> Result<Vec<Entity>, SpawnError> + Send + Sync>;
+type BoxedBatchSpawnFunction<T, D> = Box<
+    dyn FnMut(&mut World, SpawnRequest<T>, Vec<D>) -> Result<Vec<Entity>, SpawnError> + Send + Sync,
+>;
+pub(crate) type BoxedResetFunction<D> = Box<dyn FnMut(&mut World, Entity, D) + Send + Sync>;
+
+/// All spawners registered for a combination of object and user data.
+/// Spawners are grouped by the enum discriminant of their object so that looking up the spawner for an event is O(1).
+#[derive(Resource)]
+pub(crate) struct SpawnerRegistry<T, D>
+where
+    T: Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    spawners: HashMap<Discriminant<T>, Vec<(T, BoxedSpawnFunction<T, D>)>>,
+    /// Spawners registered with a [`Matcher`], in the order they were registered.
+    matchers: Vec<(Matcher<T>, BoxedSpawnFunction<T, D>)>,
+    /// Spawners registered with [`batch`], which handle [`SpawnBatchEvent`]s in one go.
+    batch_spawners: HashMap<Discriminant<T>, Vec<(T, BoxedBatchSpawnFunction<T, D>)>>,
+    /// The reset functions of all pools, indexed like the pools in [`SpawnPools`].
+    reset_functions: Vec<BoxedResetFunction<D>>,
+    warned_objects: HashSet<String>,
+    tracker: SpawnTracker,
+}
+
+impl<T, D> Default for SpawnerRegistry<T, D>
+where
+    T: Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self {
+            spawners: default(),
+            matchers: default(),
+            batch_spawners: default(),
+            reset_functions: default(),
+            warned_objects: default(),
+            tracker: default(),
+        }
+    }
+}
+
+impl<T, D> SpawnerRegistry<T, D>
+where
+    T: Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    fn insert(&mut self, object: T, spawn_function: BoxedSpawnFunction<T, D>) {
+        self.spawners
+            .entry(mem::discriminant(&object))
+            .or_default()
+            .push((object, spawn_function));
+    }
+
+    /// The number of spawners registered for exactly this object and the number of matchers that match it.
+    pub(crate) fn count(&self, object: &T) -> (usize, usize) {
+        let exact = self
+            .spawners
+            .get(&mem::discriminant(object))
+            .map_or(0, |spawners| {
+                spawners
+                    .iter()
+                    .filter(|(candidate, _)| candidate == object)
+                    .count()
+            });
+        let matched = self
+            .matchers
+            .iter()
+            .filter(|(matcher, _)| matcher.matches(object))
+            .count();
+        (exact, matched)
+    }
+
+    pub(crate) fn insert_reset_function(&mut self, reset_function: BoxedResetFunction<D>) {
+        self.reset_functions.push(reset_function);
+    }
+
+    pub(crate) fn spawn_function_mut(
+        &mut self,
+        object: &T,
+    ) -> Option<&mut BoxedSpawnFunction<T, D>> {
+        find_spawn_function(&mut self.spawners, &mut self.matchers, object)
+    }
+
+    fn spawn(&mut self, world: &mut World, event: ReadySpawnEvent<T, D>, config: &SpewConfig<T, D>)
+    where
+        T: Debug,
+    {
+        let (event, data) = event.take_data();
+        let request = SpawnRequest {
+            object: &event.object,
+            frame: event.frame,
+            correlation_id: event.correlation_id,
+            parent: event.parent,
+            spawn_point: None,
+        };
+        match self.spawn_object(world, request, event.spawn_point.as_ref(), data, config) {
+            SpawnOutcome::Spawned { entities, .. } => {
+                world.send_event(SpawnedEvent::<T, D> {
+                    object: event.object,
+                    correlation_id: event.correlation_id,
+                    entities,
+                    _data_type: std::marker::PhantomData,
+                });
+            }
+            SpawnOutcome::Skipped => {}
+            SpawnOutcome::Failed { data, error } => {
+                report_failed(world, event.with_data(data), error, config);
+            }
+        }
+    }
+
+    /// Spawn a single object with its regular spawner, or reuse a pooled entity for it.
+    /// The spawn point is only picked once the object can be spawned there, so failing early does not use up a random number or a round robin turn.
+    fn spawn_object(
+        &mut self,
+        world: &mut World,
+        mut request: SpawnRequest<T>,
+        spawn_point: Option<&SpawnPointSelector>,
+        data: D,
+        config: &SpewConfig<T, D>,
+    ) -> SpawnOutcome<D>
+    where
+        T: Debug,
+    {
+        let reset_functions = &mut self.reset_functions;
+        let tracker = &mut self.tracker;
+        let spawn_function =
+            find_spawn_function(&mut self.spawners, &mut self.matchers, request.object);
+        let Some(spawn_function) = spawn_function else {
+            report_unhandled::<T, D>(
+                request.object,
+                config.unhandled_policy,
+                &mut self.warned_objects,
+            );
+            return SpawnOutcome::Skipped;
+        };
+        if let Some(parent) = request.parent {
+            if world.get_entity(parent).is_none() {
+                warn!(
+                    "Did not spawn {:?} because its parent {parent} no longer exists",
+                    request.object
+                );
+                return SpawnOutcome::Skipped;
             }
+        }
 
-            for event in unhandled_events {
-                events.send(event);
+        let pool = world
+            .get_resource::<SpawnPools<T, D>>()
+            .and_then(|pools| pools.find(request.object));
+        if let Some(selector) = spawn_point {
+            if pool.is_some() {
+                let error = format!(
+                    "{:?} was requested at a spawn point, but it is pooled and the reset function of its pool can't receive the spawn point",
+                    request.object
+                );
+                return SpawnOutcome::Failed {
+                    data: config.failed_spawns.map(|_| data),
+                    error: error.into(),
+                };
+            }
+            match resolve_spawn_point(world, selector, request.object) {
+                Ok(spawn_point) => request.spawn_point = Some(spawn_point),
+                Err(error) => {
+                    // No spawner ran, so the data is still around and does not need to be cloned for the report.
+                    return SpawnOutcome::Failed {
+                        data: config.failed_spawns.map(|_| data),
+                        error: error.into(),
+                    };
+                }
+            }
+        }
+        let action = match pool {
+            Some(pool) => pool::acquire::<T, D>(world, pool),
+            None => PoolAction::Spawn,
+        };
+        let (entities, roots) = match action {
+            PoolAction::Skip => return SpawnOutcome::Skipped,
+            PoolAction::Reuse { pool, entity } => {
+                pool::activate(world, entity);
+                reset_functions[pool](world, entity, data);
+                (vec![entity], vec![entity])
+            }
+            PoolAction::Grow { .. } | PoolAction::Spawn => {
+                tracker.start(world);
+                // The data is moved into the spawner, so keep a copy around in case it fails and has to be reported or retried.
+                let failed_data = config
+                    .failed_spawns
+                    .map(|failed_spawns| (failed_spawns.clone_data)(&data));
+                let entities = match spawn_function(world, request, data) {
+                    Ok(entities) => entities,
+                    Err(error) => {
+                        return SpawnOutcome::Failed {
+                            data: failed_data,
+                            error,
+                        }
+                    }
+                };
+                let roots = root_entities(tracker, world);
+                if let (PoolAction::Grow { pool }, Some(&root)) = (action, roots.first()) {
+                    pool::add_to_pool::<T, D>(world, pool, root, true);
+                }
+                (entities, roots)
             }
+        };
+        insert_spewed(world, &roots, request, config);
+        if let Some(parent) = request.parent {
+            if let Some(mut parent) = world.get_entity_mut(parent) {
+                parent.push_children(&roots);
+            }
+        }
+        SpawnOutcome::Spawned { entities, roots }
+    }
 
-            for event in handled_events {
-                let mut system_state: SystemState<F::Param> = SystemState::new(world);
-                let user_data = event.data;
-                let param = system_state.get_mut(world);
-                spawn_function.run(user_data, param);
-                system_state.apply(world);
+    fn spawn_batch(
+        &mut self,
+        world: &mut World,
+        event: SpawnBatchEvent<T, D>,
+        config: &SpewConfig<T, D>,
+    ) where
+        T: Debug,
+    {
+        let frame = world
+            .get_resource::<FrameCount>()
+            .map_or(0, |frame| frame.0);
+        let request = SpawnRequest {
+            object: &event.object,
+            frame,
+            correlation_id: event.correlation_id,
+            parent: None,
+            spawn_point: None,
+        };
+        let batch_function = self
+            .batch_spawners
+            .get_mut(&mem::discriminant(&event.object))
+            .and_then(|spawners| {
+                spawners.iter_mut().find_map(|(candidate, spawn_function)| {
+                    (*candidate == event.object).then_some(spawn_function)
+                })
+            });
+        let result = match batch_function {
+            Some(batch_function) => {
+                self.tracker.start(world);
+                let result = batch_function(world, request, event.data);
+                let roots = root_entities(&mut self.tracker, world);
+                if result.is_ok() {
+                    insert_spewed(world, &roots, request, config);
+                } else {
+                    for root in roots {
+                        pool::recycle(world, root);
+                    }
+                }
+                result
+            }
+            None => {
+                if self.spawn_function_mut(&event.object).is_none() {
+                    report_unhandled::<T, D>(
+                        &event.object,
+                        config.unhandled_policy,
+                        &mut self.warned_objects,
+                    );
+                    return;
+                }
+                self.spawn_each(world, request, event.data, config)
             }
         };
-        app.add_systems(
-            Update,
-            system
-                .after(delay_spawn_events::<T, F::In>)
-                .in_set(SpewSystemSet),
+        match result {
+            Ok(entities) => {
+                world.send_event(SpawnedEvent::<T, D> {
+                    object: event.object,
+                    correlation_id: event.correlation_id,
+                    entities,
+                    _data_type: std::marker::PhantomData,
+                });
+            }
+            Err(error) => {
+                world.send_event(SpawnFailed::<T, D> {
+                    object: event.object,
+                    data: None,
+                    error: error.into(),
+                    correlation_id: event.correlation_id,
+                    attempt: 1,
+                    retrying: false,
+                });
+            }
+        }
+    }
+
+    /// Spawn a batch with the regular spawner, one instance at a time.
+    /// If an instance fails, everything spawned for the batch so far is despawned again.
+    fn spawn_each(
+        &mut self,
+        world: &mut World,
+        request: SpawnRequest<T>,
+        data: Vec<D>,
+        config: &SpewConfig<T, D>,
+    ) -> Result<Vec<Entity>, SpawnError>
+    where
+        T: Debug,
+    {
+        let mut entities = Vec::new();
+        let mut roots = Vec::new();
+        for data in data {
+            match self.spawn_object(world, request, None, data, config) {
+                SpawnOutcome::Spawned {
+                    entities: spawned_entities,
+                    roots: spawned_roots,
+                } => {
+                    entities.extend(spawned_entities);
+                    roots.extend(spawned_roots);
+                }
+                SpawnOutcome::Skipped => {}
+                SpawnOutcome::Failed { error, .. } => {
+                    for root in roots {
+                        pool::recycle(world, root);
+                    }
+                    return Err(error);
+                }
+            }
+        }
+        Ok(entities)
+    }
+}
+
+/// What happened to a single spawn request.
+enum SpawnOutcome<D> {
+    Spawned {
+        /// The entities returned by the spawner.
+        entities: Vec<Entity>,
+        /// The entities without a parent, which spew manages.
+        roots: Vec<Entity>,
+    },
+    /// Nothing was spawned, e.g. because no spawner was registered or a full pool skipped the request.
+    Skipped,
+    Failed {
+        /// A copy of the data passed to the spawner, if failed spawns are configured.
+        data: Option<D>,
+        error: SpawnError,
+    },
+}
+
+/// The entities spawned since the tracker was started that have no parent.
+fn root_entities(tracker: &mut SpawnTracker, world: &World) -> Vec<Entity> {
+    tracker
+        .spawned_entities(world)
+        .iter()
+        .copied()
+        .filter(|&entity| !world.entity(entity).contains::<Parent>())
+        .collect()
+}
+
+fn insert_spewed<T, D>(
+    world: &mut World,
+    roots: &[Entity],
+    request: SpawnRequest<T>,
+    config: &SpewConfig<T, D>,
+) where
+    T: Debug + Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    let frame = world
+        .get_resource::<FrameCount>()
+        .map_or(0, |frame| frame.0);
+    for &root in roots {
+        let spewed = Spewed::new(
+            request.object,
+            config.clone_object,
+            frame,
+            request.correlation_id,
         );
+        world.entity_mut(root).insert(spewed);
+    }
+}
+
+/// Find the spawner for an object. Spawners registered for the exact object take precedence over matchers.
+fn find_spawn_function<'a, T, D>(
+    spawners: &'a mut HashMap<Discriminant<T>, Vec<(T, BoxedSpawnFunction<T, D>)>>,
+    matchers: &'a mut [(Matcher<T>, BoxedSpawnFunction<T, D>)],
+    object: &T,
+) -> Option<&'a mut BoxedSpawnFunction<T, D>>
+where
+    T: Eq,
+{
+    let exact = spawners
+        .get_mut(&mem::discriminant(object))
+        .and_then(|spawners| {
+            spawners.iter_mut().find_map(|(candidate, spawn_function)| {
+                (candidate == object).then_some(spawn_function)
+            })
+        });
+    exact.or_else(|| {
+        matchers
+            .iter_mut()
+            .find_map(|(matcher, spawn_function)| matcher.matches(object).then_some(spawn_function))
+    })
+}
+
+pub(crate) fn spawn_ready_events<T, D>(world: &mut World)
+where
+    T: Debug + Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    let config = *world.resource::<SpewConfig<T, D>>();
+    with_registry(world, |world, registry: &mut SpawnerRegistry<T, D>| {
+        spend_budget(world, config.budget, |world, event| {
+            registry.spawn(world, event, &config);
+        });
+    });
+}
+
+/// Spawn an object right away instead of waiting for the next run of [`SpewSystemSet`](crate::prelude::SpewSystemSet).
+/// This skips the backlog and ignores the [`SpawnBudget`](crate::prelude::SpawnBudget).
+pub(crate) fn spawn_immediately<T, D>(world: &mut World, event: ReadySpawnEvent<T, D>)
+where
+    T: Debug + Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    world.send_event(event);
+    let mut deferred_spawns = world.resource_mut::<DeferredSpawns>();
+    if deferred_spawns.running > 0 {
+        // Spawning now would attribute the new entities to the spawner that is currently running,
+        // so wait until it is done.
+        deferred_spawns.flushes.push(spawn_ready_now::<T, D>);
+        return;
+    }
+    spawn_ready_now::<T, D>(world);
+}
+
+fn spawn_ready_now<T, D>(world: &mut World)
+where
+    T: Debug + Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    let config = *world.resource::<SpewConfig<T, D>>();
+    let events: Vec<_> = world
+        .resource_mut::<Events<ReadySpawnEvent<T, D>>>()
+        .drain()
+        .collect();
+    if events.is_empty() {
+        return;
+    }
+    with_registry(world, |world, registry: &mut SpawnerRegistry<T, D>| {
+        for event in events {
+            registry.spawn(world, event, &config);
+        }
+    });
+}
+
+/// Immediate spawns that were requested while a spawner was running, e.g. through [`SpewCommandsExt`](crate::prelude::SpewCommandsExt) from inside the spawner.
+#[derive(Resource, Default)]
+pub(crate) struct DeferredSpawns {
+    /// How many registries are currently taken out of the world to run spawners.
+    running: usize,
+    /// Spawn the ready events of one combination of object and data.
+    flushes: Vec<fn(&mut World)>,
+}
+
+/// Take the registry out of the world while running its spawners.
+/// Immediate spawns requested in the meantime are deferred until no spawner is running anymore.
+pub(crate) fn with_registry<T, D, R>(
+    world: &mut World,
+    run: impl FnOnce(&mut World, &mut SpawnerRegistry<T, D>) -> R,
+) -> R
+where
+    T: Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    world.resource_mut::<DeferredSpawns>().running += 1;
+    let result = world.resource_scope(|world, mut registry: Mut<SpawnerRegistry<T, D>>| {
+        run(world, &mut registry)
+    });
+    let mut deferred_spawns = world.resource_mut::<DeferredSpawns>();
+    deferred_spawns.running -= 1;
+    if deferred_spawns.running == 0 {
+        for flush in mem::take(&mut deferred_spawns.flushes) {
+            flush(world);
+        }
+    }
+    result
+}
+
+pub(crate) fn spawn_batch_events<T, D>(world: &mut World)
+where
+    T: Debug + Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    let config = *world.resource::<SpewConfig<T, D>>();
+    let events: Vec<_> = world
+        .resource_mut::<Events<SpawnBatchEvent<T, D>>>()
+        .drain()
+        .collect();
+    if events.is_empty() {
+        return;
+    }
+    with_registry(world, |world, registry: &mut SpawnerRegistry<T, D>| {
+        for event in events {
+            registry.spawn_batch(world, event, &config);
+        }
+    });
+}
+
+/// Send a [`SpawnFailed`] event and queue a retry if the [`RetryPolicy`](crate::prelude::RetryPolicy) allows it.
+/// The data of the event is only available if failed spawns are configured, as it has to be cloned before spawning.
+fn report_failed<T, D>(
+    world: &mut World,
+    event: ReadySpawnEvent<T, Option<D>>,
+    error: SpawnError,
+    config: &SpewConfig<T, D>,
+) where
+    T: Debug + Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    let attempt = event.attempt + 1;
+    let retry = match (config.failed_spawns, &event.data) {
+        (Some(failed_spawns), Some(data)) if failed_spawns.retry.allows(attempt) => {
+            Some(ReadySpawnEvent {
+                object: (failed_spawns.clone_object)(&event.object),
+                data: (failed_spawns.clone_data)(data),
+                handle: event.handle,
+                correlation_id: event.correlation_id,
+                parent: event.parent,
+                spawn_point: event.spawn_point.clone(),
+                frame: event.frame,
+                attempt,
+            })
+        }
+        _ => None,
+    };
+    let retrying = retry.is_some();
+    if let Some(retry) = retry {
+        world.resource_mut::<SpawnRetries<T, D>>().push(retry);
+    }
+    world.send_event(SpawnFailed {
+        object: event.object,
+        data: event.data,
+        error: error.into(),
+        correlation_id: event.correlation_id,
+        attempt,
+        retrying,
+    });
+}
+
+/// The error for a spawn at a spawn point whose spawner does not receive a [`SpawnContext`].
+fn spawn_point_unsupported<T: Debug>(object: &T) -> SpawnError {
+    format!(
+        "{object:?} was requested at a spawn point, but its spawner can't receive the spawn point. \
+        Wrap the spawner with `with_context` to receive it in `SpawnContext::spawn_point`."
+    )
+    .into()
+}
+
+fn report_unhandled<T, D>(object: &T, policy: UnhandledSpawnPolicy, warned: &mut HashSet<String>)
+where
+    T: Debug,
+{
+    let object = format!("{object:?}");
+    let message = || {
+        format!(
+            "No spawner was registered for {object} with data of type `{data}`, so it was not spawned. \
+            Register one with `app.add_spawner(({object}, your_spawn_function))` on an app with `SpewPlugin::<{object_type}, {data}>`.",
+            object_type = type_name::<T>(),
+            data = type_name::<D>(),
+        )
+    };
+    match policy {
+        UnhandledSpawnPolicy::Ignore => {}
+        UnhandledSpawnPolicy::WarnOnce => {
+            if !warned.contains(&object) {
+                warn!("{}", message());
+                warned.insert(object);
+            }
+        }
+        UnhandledSpawnPolicy::Warn => warn!("{}", message()),
+        UnhandledSpawnPolicy::Panic => panic!("{}", message()),
     }
 }
 
@@ -80,3 +1020,133 @@ macro_rules! impl_spawners_tuples {
 }
 
 all_tuples!(impl_spawners_tuples, 0, 15, S, D);
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use bevy::prelude::*;
+
+    #[derive(Debug, Eq, PartialEq, Clone)]
+    enum Object {
+        Flaky,
+        Wall,
+    }
+
+    #[derive(Component)]
+    struct Wall;
+
+    /// How many times the spawner ran.
+    #[derive(Resource, Default)]
+    struct Attempts(u32);
+
+    /// Fail as many times as the data says before spawning the object.
+    fn spawn_flaky(
+        In(failures): In<u32>,
+        mut attempts: ResMut<Attempts>,
+        mut commands: Commands,
+    ) -> Result<Entity, String> {
+        attempts.0 += 1;
+        if attempts.0 <= failures {
+            return Err(format!("Attempt {} failed", attempts.0));
+        }
+        Ok(commands.spawn_empty().id())
+    }
+
+    fn spawn_wall(_: In<u32>, mut commands: Commands) {
+        commands.spawn(Wall);
+    }
+
+    /// Spawn all walls at once, but fail after spawning them if one of them has no length.
+    fn spawn_walls(In(lengths): In<Vec<u32>>, mut commands: Commands) -> Result<(), &'static str> {
+        for _ in &lengths {
+            commands.spawn(Wall);
+        }
+        if lengths.contains(&0) {
+            return Err("A wall needs a length");
+        }
+        Ok(())
+    }
+
+    fn app(plugin: SpewPlugin<Object, u32>) -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<Attempts>()
+            .add_plugins(plugin)
+            .add_spawners((
+                (Object::Flaky, spawn_flaky),
+                (Object::Wall, spawn_wall),
+                (Object::Wall, batch(spawn_walls)),
+            ));
+        app
+    }
+
+    /// Run the app once and return the data, attempt and retry flag of every failed spawn and the number of spawned objects in that run.
+    fn update(app: &mut App) -> (Vec<(Option<u32>, u32, bool)>, usize) {
+        app.update();
+        let world = app.world_mut();
+        let failed = world
+            .resource_mut::<Events<SpawnFailed<Object, u32>>>()
+            .drain()
+            .map(|failed| (failed.data, failed.attempt, failed.retrying))
+            .collect();
+        let spawned = world
+            .resource_mut::<Events<SpawnedEvent<Object, u32>>>()
+            .drain()
+            .count();
+        (failed, spawned)
+    }
+
+    fn send(app: &mut App, event: SpawnEvent<Object, u32>) {
+        app.world_mut().send_event(event);
+    }
+
+    #[test]
+    fn retry_until_spawned() {
+        let mut app =
+            app(SpewPlugin::default().failed_spawns(RetryPolicy::NextFrame { max_retries: 3 }));
+        send(&mut app, SpawnEvent::with_data(Object::Flaky, 2));
+        assert_eq!(update(&mut app), (vec![(Some(2), 1, true)], 0));
+        assert_eq!(update(&mut app), (vec![(Some(2), 2, true)], 0));
+        assert_eq!(update(&mut app), (vec![], 1));
+        assert_eq!(update(&mut app), (vec![], 0));
+        assert_eq!(app.world().resource::<Attempts>().0, 3);
+    }
+
+    #[test]
+    fn give_up_after_max_retries() {
+        let mut app =
+            app(SpewPlugin::default().failed_spawns(RetryPolicy::NextFrame { max_retries: 1 }));
+        send(&mut app, SpawnEvent::with_data(Object::Flaky, 5));
+        assert_eq!(update(&mut app), (vec![(Some(5), 1, true)], 0));
+        assert_eq!(update(&mut app), (vec![(Some(5), 2, false)], 0));
+        assert_eq!(update(&mut app), (vec![], 0));
+        assert_eq!(app.world().resource::<Attempts>().0, 2);
+    }
+
+    #[test]
+    fn report_without_retrying_by_default() {
+        let mut app = app(SpewPlugin::default());
+        send(&mut app, SpawnEvent::with_data(Object::Flaky, 1));
+        assert_eq!(update(&mut app), (vec![(None, 1, false)], 0));
+        assert_eq!(update(&mut app), (vec![], 0));
+        assert_eq!(app.world().resource::<Attempts>().0, 1);
+    }
+
+    #[test]
+    fn despawn_failed_batch() {
+        let mut app =
+            app(SpewPlugin::default().failed_spawns(RetryPolicy::NextFrame { max_retries: 3 }));
+        app.world_mut()
+            .send_event(SpawnBatchEvent::<Object, u32>::new(Object::Wall, [3, 0, 5]));
+        // Batches are never retried
+        assert_eq!(update(&mut app), (vec![(None, 1, false)], 0));
+        let world = app.world_mut();
+        assert_eq!(world.query::<&Wall>().iter(world).count(), 0);
+
+        app.world_mut()
+            .send_event(SpawnBatchEvent::<Object, u32>::new(Object::Wall, [3, 5]));
+        assert_eq!(update(&mut app), (vec![], 1));
+        let world = app.world_mut();
+        assert_eq!(world.query::<&Wall>().iter(world).count(), 2);
+    }
+}
@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+
+#[cfg(feature = "derive")]
+pub use spew_derive::{SpewVariants, Spewable};
+
+/// An object enum that knows the spawners of all of its variants, so that they can be registered with [`SpewApp::add_spewable`](crate::prelude::SpewApp::add_spewable).
+/// With the `derive` feature, this trait can be derived by annotating every variant with `#[spawner(your_spawn_function)]`,
+/// which also checks at compile time that no variant was forgotten.
+///
+/// # Example
+/// ```rust
+/// use spew::prelude::*;
+/// use bevy::prelude::*;
+///
+/// #[derive(Debug, Eq, PartialEq)]
+/// enum Object {
+///    Cube,
+///    Sphere,
+/// }
+///
+/// impl Spewable for Object {
+///     fn add_spawners(app: &mut App) {
+///         app.add_spawner((Object::Cube, spawn_cube))
+///             .add_spawner((Object::Sphere, spawn_sphere));
+///     }
+/// }
+///
+/// App::new()
+///     .add_plugins(SpewPlugin::<Object, Transform>::default())
+///     .add_spewable::<Object>();
+///
+/// fn spawn_cube(In(transform): In<Transform>, mut commands: Commands) {
+///     commands.spawn((Name::new("Cube"), transform));
+/// }
+///
+/// fn spawn_sphere(In(transform): In<Transform>, mut commands: Commands) {
+///     commands.spawn((Name::new("Sphere"), transform));
+/// }
+/// ```
+pub trait Spewable {
+    /// Add the spawners of all variants to the app. Called internally.
+    fn add_spawners(app: &mut App);
+}
+
+/// An object enum that can list all of its variants, so that [`SpewPlugin::validate_variants`](crate::prelude::SpewPlugin::validate_variants)
+/// can check that each of them has exactly one spawner.
+/// With the `derive` feature, this trait can be derived for enums without fields.
+///
+/// # Example
+/// ```rust
+/// use spew::prelude::*;
+///
+/// #[derive(Debug, Eq, PartialEq)]
+/// enum Object {
+///    Cube,
+///    Sphere,
+/// }
+///
+/// impl SpewVariants for Object {
+///     fn variants() -> Vec<Self> {
+///         vec![Object::Cube, Object::Sphere]
+///     }
+/// }
+/// ```
+pub trait SpewVariants: Sized {
+    /// All variants that need a spawner.
+    fn variants() -> Vec<Self>;
+}
@@ -0,0 +1,38 @@
+use bevy::ecs::system::{SystemParamFunction, SystemState};
+use bevy::prelude::*;
+
+/// A user-provided function that is run like a system, together with the [`SystemState`] of its parameters.
+///
+/// The state is created on the first run and reused afterwards, so `Local`s keep their values between runs,
+/// change detection compares against the previous run and running the function does not allocate a new state each time.
+pub(crate) struct CachedSystem<F, Marker>
+where
+    F: SystemParamFunction<Marker>,
+    Marker: 'static,
+{
+    function: F,
+    state: Option<SystemState<F::Param>>,
+}
+
+impl<F, Marker> CachedSystem<F, Marker>
+where
+    F: SystemParamFunction<Marker>,
+    Marker: 'static,
+{
+    pub(crate) fn new(function: F) -> Self {
+        Self {
+            function,
+            state: None,
+        }
+    }
+
+    /// Run the function and apply its deferred changes, like spawning entities with `Commands`.
+    pub(crate) fn run(&mut self, world: &mut World, input: F::In) -> F::Out {
+        let state = self.state.get_or_insert_with(|| SystemState::new(world));
+        // Fetching the parameters also updates the archetypes that queries look at since the last run.
+        let param = state.get_mut(world);
+        let output = self.function.run(input, param);
+        state.apply(world);
+        output
+    }
+}
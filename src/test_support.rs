@@ -0,0 +1,45 @@
+//! Fixtures shared by the tests that drive a whole [`App`].
+
+use crate::prelude::*;
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use std::time::Duration;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub(crate) enum Object {
+    Marker,
+}
+
+/// The data of every [`Object::Marker`] spawned since it was last taken out by [`update`].
+#[derive(Resource, Default)]
+pub(crate) struct Spawned(pub(crate) Vec<u32>);
+
+pub(crate) fn record(In(id): In<u32>, mut spawned: ResMut<Spawned>) {
+    spawned.0.push(id);
+}
+
+/// An app with the given plugin that records the data of every spawned [`Object::Marker`].
+/// Every update advances the time by 100 milliseconds.
+pub(crate) fn app(plugin: SpewPlugin<Object, u32>) -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )))
+        .init_resource::<Spawned>()
+        .add_plugins(plugin)
+        .add_spawner((Object::Marker, record));
+    app
+}
+
+pub(crate) fn send(app: &mut App, event: SpawnEvent<Object, u32>) -> SpawnHandle {
+    let handle = event.handle;
+    app.world_mut().send_event(event);
+    handle
+}
+
+/// Run the app once and return what was spawned in that run.
+pub(crate) fn update(app: &mut App) -> Vec<u32> {
+    app.update();
+    std::mem::take(&mut app.world_mut().resource_mut::<Spawned>().0)
+}
@@ -0,0 +1,185 @@
+use bevy::ecs::archetype::Archetype;
+use bevy::ecs::component::Tick;
+use bevy::prelude::*;
+
+/// Finds the entities that were spawned while a spawner was running.
+///
+/// An entity is new if all of its components were added since the tracker was started.
+/// New entities are appended to the end of an archetype, so usually only the entities past the previous length of each archetype need to be checked.
+/// Entities only end up further in front if the spawner removed an entity from an archetype, i.e. despawned it, removed a component from it
+/// or moved it to another archetype by inserting a component. The first two send removal events and the last one appends an older entity to another archetype,
+/// so in both cases the tracker notices and checks all entities instead.
+///
+/// The buffers are kept between spawns, so tracking a spawn does not allocate once they are large enough.
+pub(crate) struct SpawnTracker {
+    archetype_lens: Vec<usize>,
+    removals: usize,
+    tick: Tick,
+    spawned: Vec<Entity>,
+}
+
+impl Default for SpawnTracker {
+    fn default() -> Self {
+        Self {
+            archetype_lens: Vec::new(),
+            removals: 0,
+            tick: Tick::new(0),
+            spawned: Vec::new(),
+        }
+    }
+}
+
+impl SpawnTracker {
+    /// Remember the state of the world before a spawner runs.
+    pub(crate) fn start(&mut self, world: &World) {
+        self.archetype_lens.clear();
+        self.archetype_lens
+            .extend(world.archetypes().iter().map(Archetype::len));
+        self.removals = removal_count(world);
+        self.tick = world.read_change_tick();
+    }
+
+    /// The entities spawned since [`SpawnTracker::start`] that still exist, sorted by their id.
+    pub(crate) fn spawned_entities(&mut self, world: &World) -> &[Entity] {
+        let this_run = world.read_change_tick();
+        self.spawned.clear();
+        let mut complete = removal_count(world) == self.removals;
+        for archetype in world.archetypes().iter() {
+            for candidate in &archetype.entities()[self.old_len(archetype)..] {
+                if self.is_new(world, archetype, candidate.id(), this_run) {
+                    self.spawned.push(candidate.id());
+                } else {
+                    complete = false;
+                }
+            }
+        }
+        if !complete {
+            self.spawned.clear();
+            for archetype in world.archetypes().iter() {
+                // Without components, new entities can only be told apart from older ones by their position.
+                let first = if archetype.component_count() == 0 {
+                    self.old_len(archetype)
+                } else {
+                    0
+                };
+                for candidate in &archetype.entities()[first..] {
+                    if self.is_new(world, archetype, candidate.id(), this_run) {
+                        self.spawned.push(candidate.id());
+                    }
+                }
+            }
+        }
+        // Archetypes are visited in an arbitrary order, so sort the result to keep it deterministic.
+        self.spawned.sort_unstable();
+        &self.spawned
+    }
+
+    /// The length of the archetype when the tracker was started, capped at its current length.
+    fn old_len(&self, archetype: &Archetype) -> usize {
+        self.archetype_lens
+            .get(archetype.id().index())
+            .map_or(0, |&old_len| old_len.min(archetype.len()))
+    }
+
+    fn is_new(&self, world: &World, archetype: &Archetype, entity: Entity, this_run: Tick) -> bool {
+        let entity = world.entity(entity);
+        archetype.components().all(|component_id| {
+            entity
+                .get_change_ticks_by_id(component_id)
+                .is_some_and(|ticks| ticks.is_added(self.tick, this_run))
+        })
+    }
+}
+
+/// The number of removal events sent so far, which grows whenever an entity is despawned or a component is removed.
+fn removal_count(world: &World) -> usize {
+    world
+        .removed_components()
+        .iter()
+        .map(|(_, events)| events.len())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use bevy::prelude::*;
+
+    #[derive(Debug, Eq, PartialEq)]
+    enum Object {
+        /// Despawns an old turret before spawning a new one.
+        Replacement,
+        /// Spawns a new turret before despawning an old one.
+        Reinforcement,
+        /// Spawns a new turret and upgrades an old one.
+        Upgrade,
+    }
+
+    #[derive(Component)]
+    struct Turret;
+
+    #[derive(Component)]
+    struct Upgraded;
+
+    fn replace_turret(mut commands: Commands, turrets: Query<Entity, With<Turret>>) {
+        if let Some(turret) = turrets.iter().next() {
+            commands.entity(turret).despawn();
+        }
+        commands.spawn(Turret);
+    }
+
+    fn reinforce_turrets(mut commands: Commands, turrets: Query<Entity, With<Turret>>) {
+        commands.spawn(Turret);
+        if let Some(turret) = turrets.iter().next() {
+            commands.entity(turret).despawn();
+        }
+    }
+
+    fn upgrade_turret(mut commands: Commands, turrets: Query<Entity, With<Turret>>) {
+        commands.spawn(Turret);
+        if let Some(turret) = turrets.iter().next() {
+            commands.entity(turret).insert(Upgraded);
+        }
+    }
+
+    /// Spawn the object next to a few existing turrets and return how many turrets were marked as spewed.
+    fn spawn_with_parent(object: Object) -> usize {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(SpewPlugin::<Object>::default())
+            .add_spawners((
+                (Object::Replacement, replace_turret),
+                (Object::Reinforcement, reinforce_turrets),
+                (Object::Upgrade, upgrade_turret),
+            ));
+        app.world_mut().spawn_batch([Turret, Turret, Turret]);
+        let parent = app.world_mut().spawn_empty().id();
+        app.world_mut()
+            .send_event(SpawnEvent::<Object>::new(object).with_parent(parent));
+        app.update();
+
+        let mut spewed = app
+            .world_mut()
+            .query_filtered::<(Entity, &Parent), (With<Turret>, With<Spewed<Object>>)>();
+        let spewed: Vec<_> = spewed.iter(app.world()).collect();
+        assert!(spewed
+            .iter()
+            .all(|(_, turret_parent)| turret_parent.get() == parent));
+        spewed.len()
+    }
+
+    #[test]
+    fn despawn_then_spawn() {
+        assert_eq!(spawn_with_parent(Object::Replacement), 1);
+    }
+
+    #[test]
+    fn spawn_then_despawn() {
+        assert_eq!(spawn_with_parent(Object::Reinforcement), 1);
+    }
+
+    #[test]
+    fn spawn_and_move_old_entity() {
+        assert_eq!(spawn_with_parent(Object::Upgrade), 1);
+    }
+}
@@ -0,0 +1,533 @@
+use crate::budget::SpawnBacklog;
+use crate::despawner::Spewed;
+use crate::events::{Delay, SpawnEvent, SpawnHandle, SpawnRetries};
+use crate::pending::PendingSpawns;
+use crate::pool::InPool;
+use bevy::prelude::*;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Correlation ids of waves have the highest bit set, so they don't collide with the small ids usually picked by hand.
+const WAVE_CORRELATION_BIT: u64 = 1 << 63;
+
+/// A wave of objects that is spawned group by group. Start it with a [`StartWave`] event.
+///
+/// The groups are spawned one after another: each group starts once all objects of the previous group were spawned.
+/// A wave is completed depending on its [`WaveCompletion`], after which the next wave that was started in the meantime begins.
+/// Spew reports the progress in the [`WaveState`] resource and sends [`WaveStarted`] and [`WaveCompleted`] events.
+///
+/// All objects of a wave are spawned with the same correlation id, which is reported in [`WaveState::correlation_id`] and the [`Spewed`] component of the spawned entities.
+///
+/// # Example
+/// ```rust
+/// use spew::prelude::*;
+/// use bevy::prelude::*;
+/// use std::time::Duration;
+///
+/// #[derive(Debug, Eq, PartialEq, Clone)]
+/// enum Enemy {
+///    Goblin,
+///    Troll,
+/// }
+///
+/// fn start_first_wave(mut wave_events: EventWriter<StartWave<Enemy, Transform>>) {
+///     let wave = SpawnWave::new()
+///         .group(
+///             WaveGroup::new(Enemy::Goblin, 10, |index| Transform::from_xyz(index as f32, 0.0, 0.0))
+///                 .interval(Delay::Seconds(Duration::from_secs(1))),
+///         )
+///         .group(WaveGroup::new(Enemy::Troll, 1, |_| Transform::default()));
+///     wave_events.send(StartWave(wave));
+/// }
+/// ```
+pub struct SpawnWave<T, D = ()> {
+    /// The groups of the wave, in the order they are spawned in.
+    pub groups: Vec<WaveGroup<T, D>>,
+    /// When the wave is considered completed.
+    pub completion: WaveCompletion,
+}
+
+impl<T, D> Default for SpawnWave<T, D> {
+    fn default() -> Self {
+        Self {
+            groups: Vec::new(),
+            completion: default(),
+        }
+    }
+}
+
+impl<T, D> SpawnWave<T, D> {
+    /// Create a wave without any groups.
+    pub fn new() -> Self {
+        default()
+    }
+
+    /// Add a group that is spawned after all groups added before.
+    pub fn group(mut self, group: WaveGroup<T, D>) -> Self {
+        self.groups.push(group);
+        self
+    }
+
+    /// Set when the wave is considered completed. The default is [`WaveCompletion::AllDespawned`].
+    pub fn complete_when(mut self, completion: WaveCompletion) -> Self {
+        self.completion = completion;
+        self
+    }
+}
+
+/// When a [`SpawnWave`] is considered completed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WaveCompletion {
+    /// Once all objects of the wave were spawned.
+    AllSpawned,
+    /// Once all objects of the wave were spawned and all root entities spawned for them were despawned or returned to their pool.
+    #[default]
+    AllDespawned,
+}
+
+/// A number of objects of the same kind that are spawned as part of a [`SpawnWave`].
+pub struct WaveGroup<T, D = ()> {
+    /// The object to spawn.
+    pub object: T,
+    /// How many times to spawn the object.
+    pub count: u32,
+    /// The delay before the first spawn of the group and between two spawns.
+    /// The interval is meant to be a relative delay like [`Delay::Frames`] or [`Delay::Seconds`].
+    pub interval: Delay,
+    data: Box<dyn Fn(u32) -> D + Send + Sync>,
+    clone_object: fn(&T) -> T,
+}
+
+impl<T, D> WaveGroup<T, D>
+where
+    T: Clone,
+{
+    /// Create a group that spawns the object `count` times. Without an [`interval`](WaveGroup::interval), all of them are spawned at once.
+    /// The user data of each spawn is generated by calling `data` with the index of the spawn within the group.
+    pub fn new(object: T, count: u32, data: impl Fn(u32) -> D + Send + Sync + 'static) -> Self {
+        Self {
+            object,
+            count,
+            interval: default(),
+            data: Box::new(data),
+            clone_object: T::clone,
+        }
+    }
+
+    /// Set the delay before the first spawn of the group and between two spawns.
+    pub fn interval(mut self, interval: Delay) -> Self {
+        self.interval = interval;
+        self
+    }
+}
+
+/// An event that starts a [`SpawnWave`]. If a wave of the same object and data type is still running, the wave starts after all waves started before it are completed.
+#[derive(Event)]
+pub struct StartWave<T, D = ()>(pub SpawnWave<T, D>)
+where
+    T: Send + Sync + 'static,
+    D: Send + Sync + 'static;
+
+/// An event that is sent when a [`SpawnWave`] starts spawning.
+#[derive(Event)]
+pub struct WaveStarted<T, D = ()>
+where
+    T: Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    /// How many waves were started before this one.
+    pub number: u32,
+    /// The correlation id all objects of the wave are spawned with.
+    pub correlation_id: u64,
+    pub(crate) _spawner_enum_type: std::marker::PhantomData<(T, D)>,
+}
+
+/// An event that is sent when a [`SpawnWave`] is completed. See [`WaveCompletion`].
+#[derive(Event)]
+pub struct WaveCompleted<T, D = ()>
+where
+    T: Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    /// How many waves were started before this one.
+    pub number: u32,
+    /// The correlation id all objects of the wave were spawned with.
+    pub correlation_id: u64,
+    pub(crate) _spawner_enum_type: std::marker::PhantomData<(T, D)>,
+}
+
+/// The progress of the [`SpawnWave`]s of an object and data type.
+///
+/// # Example
+/// ```rust
+/// use spew::prelude::*;
+/// use bevy::prelude::*;
+///
+/// #[derive(Debug, Eq, PartialEq)]
+/// enum Enemy {
+///    Goblin
+/// }
+///
+/// fn show_progress(wave_state: Res<WaveState<Enemy>>) {
+///     if let Some(number) = wave_state.number() {
+///         info!("Wave {}: {} enemies left", number + 1, wave_state.alive());
+///     }
+/// }
+/// ```
+#[derive(Resource)]
+pub struct WaveState<T, D = ()>
+where
+    T: Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    active: Option<ActiveWave<T, D>>,
+    queued: VecDeque<SpawnWave<T, D>>,
+    started: u32,
+}
+
+struct ActiveWave<T, D> {
+    wave: SpawnWave<T, D>,
+    number: u32,
+    correlation_id: u64,
+    /// The index of the group that is currently spawning, or the number of groups once all of them were spawned.
+    group: usize,
+    /// The spawn events of the current group.
+    spawns: Vec<SpawnHandle>,
+    /// How many root entities spawned for the wave were still around the last time the wave was checked.
+    alive: usize,
+}
+
+impl<T, D> Default for WaveState<T, D>
+where
+    T: Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    fn default() -> Self {
+        Self {
+            active: None,
+            queued: default(),
+            started: 0,
+        }
+    }
+}
+
+impl<T, D> WaveState<T, D>
+where
+    T: Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    /// Whether a wave is running right now.
+    pub fn is_running(&self) -> bool {
+        self.active.is_some()
+    }
+
+    /// How many waves were started before the running one, or `None` if no wave is running.
+    pub fn number(&self) -> Option<u32> {
+        self.active.as_ref().map(|active| active.number)
+    }
+
+    /// The correlation id the objects of the running wave are spawned with.
+    pub fn correlation_id(&self) -> Option<u64> {
+        self.active.as_ref().map(|active| active.correlation_id)
+    }
+
+    /// The index of the group of the running wave that is currently spawning.
+    /// Returns `None` if no wave is running or all groups of the running wave were spawned.
+    pub fn group(&self) -> Option<usize> {
+        self.active
+            .as_ref()
+            .filter(|active| active.group < active.wave.groups.len())
+            .map(|active| active.group)
+    }
+
+    /// How many root entities spawned for the running wave are still around.
+    /// Only updated once all groups of the wave were spawned.
+    pub fn alive(&self) -> usize {
+        self.active.as_ref().map_or(0, |active| active.alive)
+    }
+
+    /// How many waves are waiting for the running one to be completed.
+    pub fn queued(&self) -> usize {
+        self.queued.len()
+    }
+
+    /// How many waves were started so far.
+    pub fn started(&self) -> u32 {
+        self.started
+    }
+}
+
+impl<T, D> ActiveWave<T, D>
+where
+    T: Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    /// Send the spawn events of the current group.
+    fn spawn_group(&mut self, spawn_events: &mut EventWriter<SpawnEvent<T, D>>) {
+        let Some(group) = self.wave.groups.get(self.group) else {
+            return;
+        };
+        let events: Vec<_> = (0..group.count)
+            .map(|index| {
+                let mut event =
+                    SpawnEvent::with_data((group.clone_object)(&group.object), (group.data)(index));
+                event.delay = group.interval.times(index + 1);
+                event.correlation_id = Some(self.correlation_id);
+                event
+            })
+            .collect();
+        self.spawns = events.iter().map(|event| event.handle).collect();
+        spawn_events.send_batch(events);
+    }
+}
+
+pub(crate) fn direct_waves<T, D>(
+    mut start_events: ResMut<Events<StartWave<T, D>>>,
+    mut wave_state: ResMut<WaveState<T, D>>,
+    pending_spawns: Res<PendingSpawns<T, D>>,
+    backlog: Res<SpawnBacklog<T, D>>,
+    retries: Res<SpawnRetries<T, D>>,
+    spewed: Query<&Spewed<T>, Without<InPool>>,
+    mut spawn_events: EventWriter<SpawnEvent<T, D>>,
+    mut started_events: EventWriter<WaveStarted<T, D>>,
+    mut completed_events: EventWriter<WaveCompleted<T, D>>,
+) where
+    T: Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    static NEXT_WAVE: AtomicU64 = AtomicU64::new(0);
+
+    let wave_state = &mut *wave_state;
+    wave_state
+        .queued
+        .extend(start_events.drain().map(|StartWave(wave)| wave));
+    loop {
+        let Some(active) = &mut wave_state.active else {
+            let Some(wave) = wave_state.queued.pop_front() else {
+                return;
+            };
+            let mut active = ActiveWave {
+                wave,
+                number: wave_state.started,
+                correlation_id: NEXT_WAVE.fetch_add(1, Ordering::Relaxed) | WAVE_CORRELATION_BIT,
+                group: 0,
+                spawns: Vec::new(),
+                alive: 0,
+            };
+            wave_state.started += 1;
+            started_events.send(WaveStarted {
+                number: active.number,
+                correlation_id: active.correlation_id,
+                _spawner_enum_type: std::marker::PhantomData,
+            });
+            active.spawn_group(&mut spawn_events);
+            wave_state.active = Some(active);
+            return;
+        };
+        if active.spawns.iter().any(|&handle| {
+            pending_spawns.contains(handle) || backlog.contains(handle) || retries.contains(handle)
+        }) {
+            return;
+        }
+        if active.group < active.wave.groups.len() {
+            active.group += 1;
+            active.spawn_group(&mut spawn_events);
+            if active.group < active.wave.groups.len() {
+                return;
+            }
+        }
+        active.alive = spewed
+            .iter()
+            .filter(|spewed| spewed.correlation_id == Some(active.correlation_id))
+            .count();
+        let completed = match active.wave.completion {
+            WaveCompletion::AllSpawned => true,
+            WaveCompletion::AllDespawned => active.alive == 0,
+        };
+        if !completed {
+            return;
+        }
+        completed_events.send(WaveCompleted {
+            number: active.number,
+            correlation_id: active.correlation_id,
+            _spawner_enum_type: std::marker::PhantomData,
+        });
+        wave_state.active = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+    use crate::test_support::{update, Spawned};
+    use bevy::prelude::*;
+
+    #[derive(Debug, Eq, PartialEq, Clone)]
+    enum Enemy {
+        Goblin,
+        /// Fails the first time it is spawned.
+        Troll,
+    }
+
+    #[derive(Component)]
+    struct Monster;
+
+    fn spawn_goblin(
+        In(id): In<u32>,
+        mut spawned: ResMut<Spawned>,
+        mut commands: Commands,
+    ) -> Entity {
+        spawned.0.push(id);
+        commands.spawn(Monster).id()
+    }
+
+    fn reset_goblin(In(_): In<(Entity, u32)>) {}
+
+    fn spawn_troll(
+        In(id): In<u32>,
+        mut failed: Local<bool>,
+        mut spawned: ResMut<Spawned>,
+        mut commands: Commands,
+    ) -> Result<Entity, &'static str> {
+        if !*failed {
+            *failed = true;
+            return Err("the troll is not ready yet");
+        }
+        spawned.0.push(id);
+        Ok(commands.spawn(Monster).id())
+    }
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .init_resource::<Spawned>()
+            .add_plugins(
+                SpewPlugin::<Enemy, u32>::default()
+                    .failed_spawns(RetryPolicy::NextFrame { max_retries: 1 }),
+            )
+            .add_spawners(((Enemy::Goblin, spawn_goblin), (Enemy::Troll, spawn_troll)))
+            .add_pool(Enemy::Goblin, reset_goblin, PoolConfig::default());
+        app
+    }
+
+    fn goblins(count: u32, first_id: u32) -> WaveGroup<Enemy, u32> {
+        WaveGroup::new(Enemy::Goblin, count, move |index| first_id + index)
+    }
+
+    fn start(app: &mut App, wave: SpawnWave<Enemy, u32>) {
+        app.world_mut().send_event(StartWave(wave));
+    }
+
+    /// The numbers of the waves that started and completed since the last call.
+    fn wave_events(app: &mut App) -> (Vec<u32>, Vec<u32>) {
+        let started = app
+            .world_mut()
+            .resource_mut::<Events<WaveStarted<Enemy, u32>>>()
+            .drain()
+            .map(|event| event.number)
+            .collect();
+        let completed = app
+            .world_mut()
+            .resource_mut::<Events<WaveCompleted<Enemy, u32>>>()
+            .drain()
+            .map(|event| event.number)
+            .collect();
+        (started, completed)
+    }
+
+    fn wave_state(app: &App) -> &WaveState<Enemy, u32> {
+        app.world().resource::<WaveState<Enemy, u32>>()
+    }
+
+    #[test]
+    fn spawn_groups_one_after_another() {
+        let mut app = app();
+        start(
+            &mut app,
+            SpawnWave::new()
+                .group(goblins(2, 10).interval(Delay::Frames(1)))
+                .group(goblins(2, 20))
+                .complete_when(WaveCompletion::AllSpawned),
+        );
+
+        assert!(update(&mut app).is_empty());
+        assert_eq!(wave_state(&app).group(), Some(0));
+        assert_eq!(update(&mut app), [10]);
+        assert_eq!(update(&mut app), [11]);
+        assert_eq!(wave_events(&mut app), (vec![0], vec![]));
+        assert_eq!(update(&mut app), [20, 21]);
+        assert_eq!(wave_state(&app).group(), Some(1));
+        assert_eq!(wave_events(&mut app), (vec![], vec![]));
+        assert!(update(&mut app).is_empty());
+        assert_eq!(wave_events(&mut app), (vec![], vec![0]));
+        assert!(!wave_state(&app).is_running());
+    }
+
+    #[test]
+    fn start_queued_wave_after_completion() {
+        let mut app = app();
+        start(
+            &mut app,
+            SpawnWave::new()
+                .group(goblins(1, 10))
+                .complete_when(WaveCompletion::AllSpawned),
+        );
+        start(
+            &mut app,
+            SpawnWave::new()
+                .group(goblins(1, 20))
+                .complete_when(WaveCompletion::AllSpawned),
+        );
+
+        assert_eq!(update(&mut app), [10]);
+        assert_eq!(wave_state(&app).number(), Some(0));
+        assert_eq!(wave_state(&app).queued(), 1);
+        assert_eq!(wave_events(&mut app), (vec![0], vec![]));
+        assert_eq!(update(&mut app), [20]);
+        assert_eq!(wave_state(&app).number(), Some(1));
+        assert_eq!(wave_state(&app).queued(), 0);
+        assert_eq!(wave_events(&mut app), (vec![1], vec![0]));
+        assert!(update(&mut app).is_empty());
+        assert_eq!(wave_events(&mut app), (vec![], vec![1]));
+        assert_eq!(wave_state(&app).started(), 2);
+    }
+
+    #[test]
+    fn complete_once_all_entities_are_despawned_or_pooled() {
+        let mut app = app();
+        start(&mut app, SpawnWave::new().group(goblins(2, 10)));
+
+        assert_eq!(update(&mut app), [10, 11]);
+        update(&mut app);
+        assert_eq!(wave_state(&app).alive(), 2);
+        let mut monsters = app.world_mut().query_filtered::<Entity, With<Monster>>();
+        let monsters: Vec<_> = monsters.iter(app.world()).collect();
+
+        app.world_mut().despawn(monsters[0]);
+        update(&mut app);
+        assert_eq!(wave_state(&app).alive(), 1);
+        assert_eq!(wave_events(&mut app), (vec![0], vec![]));
+
+        app.world_mut().commands().recycle(monsters[1]);
+        app.world_mut().flush();
+        update(&mut app);
+        assert_eq!(wave_events(&mut app), (vec![], vec![0]));
+        assert!(!wave_state(&app).is_running());
+    }
+
+    #[test]
+    fn wait_for_failed_spawns_to_be_retried() {
+        let mut app = app();
+        start(
+            &mut app,
+            SpawnWave::new()
+                .group(WaveGroup::new(Enemy::Troll, 1, |_| 10))
+                .group(goblins(1, 20))
+                .complete_when(WaveCompletion::AllSpawned),
+        );
+
+        assert!(update(&mut app).is_empty());
+        assert_eq!(update(&mut app), [10]);
+        assert_eq!(update(&mut app), [20]);
+    }
+}
@@ -0,0 +1,335 @@
+use crate::events::SpawnEvent;
+use bevy::prelude::*;
+
+/// The random number generator spew uses to pick objects, e.g. for [`WeightedSpawn`]s.
+/// It always starts with the same seed, so that runs are reproducible. Insert your own with [`SpewRng::seeded`] to change it.
+///
+/// # Example
+/// ```rust
+/// use spew::prelude::*;
+/// use bevy::prelude::*;
+///
+/// #[derive(Debug, Eq, PartialEq)]
+/// enum Enemy {
+///    Goblin
+/// }
+///
+/// App::new()
+///     .add_plugins(SpewPlugin::<Enemy>::default())
+///     .insert_resource(SpewRng::seeded(42));
+/// ```
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct SpewRng {
+    state: u64,
+}
+
+impl Default for SpewRng {
+    fn default() -> Self {
+        Self::seeded(0)
+    }
+}
+
+impl SpewRng {
+    /// Create a generator that produces the same numbers every time it is created with the same seed.
+    pub fn seeded(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Generate the next random number. This is [SplitMix64](https://prng.di.unimi.it/splitmix64.c).
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Generate a random number in `0.0..1.0`.
+    pub fn next_f64(&mut self) -> f64 {
+        // The upper 53 bits fill the mantissa of an f64 exactly.
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Pick the index of one of the weights, with a probability proportional to its weight.
+    /// Weights that are not positive are never picked. Returns `None` if no weight is positive.
+    pub fn pick_weighted(&mut self, weights: impl IntoIterator<Item = f32>) -> Option<usize> {
+        let weights: Vec<f64> = weights.into_iter().map(sanitize_weight).collect();
+        let total: f64 = weights.iter().sum();
+        if total <= 0.0 {
+            return None;
+        }
+        let mut target = self.next_f64() * total;
+        let mut last_positive = None;
+        for (index, weight) in weights.into_iter().enumerate() {
+            if weight <= 0.0 {
+                continue;
+            }
+            if target < weight {
+                return Some(index);
+            }
+            target -= weight;
+            last_positive = Some(index);
+        }
+        // Rounding errors can leave a tiny remainder after the last weight.
+        last_positive
+    }
+}
+
+fn sanitize_weight(weight: f32) -> f64 {
+    if weight.is_finite() && weight > 0.0 {
+        weight as f64
+    } else {
+        0.0
+    }
+}
+
+/// An event that picks objects from a weighted list with [`SpewRng`] and spawns them with their regular spawners,
+/// like sending a [`SpawnEvent`] for each picked object.
+/// The weights don't need to add up to anything in particular. Objects with a weight that is not positive are never picked.
+///
+/// By default, a single object is picked. Use [`WeightedSpawn::pick`] to pick multiple objects independently of each other,
+/// or [`WeightedSpawn::pick_unique`] to pick them without replacement.
+///
+/// # Example
+/// ```rust
+/// use spew::prelude::*;
+/// use bevy::prelude::*;
+///
+/// #[derive(Debug, Eq, PartialEq, Clone)]
+/// enum Enemy {
+///    Goblin,
+///    Orc,
+///    Troll,
+/// }
+///
+/// fn spawn_encounter(mut weighted_spawns: EventWriter<WeightedSpawn<Enemy, Transform>>) {
+///     weighted_spawns.send(
+///         WeightedSpawn::with_data(
+///             [(Enemy::Goblin, 60.0), (Enemy::Orc, 30.0), (Enemy::Troll, 10.0)],
+///             Transform::from_xyz(1.0, 2.0, 3.0),
+///         )
+///         .pick(3),
+///     );
+/// }
+/// ```
+#[derive(Event)]
+pub struct WeightedSpawn<T, D = ()>
+where
+    T: Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    /// The objects to pick from, together with their weights.
+    pub choices: Vec<(T, f32)>,
+    /// The user-provided data to pass to the spawner of every picked object.
+    pub data: D,
+    /// How many objects to pick.
+    pub count: usize,
+    /// Whether every object can be picked at most once.
+    /// If fewer objects than [`WeightedSpawn::count`] have a positive weight, all of them are picked.
+    pub unique: bool,
+    /// An optional caller-supplied id that is passed on to the [`SpawnEvent`] of every picked object.
+    pub correlation_id: Option<u64>,
+    clone: fn(&T, &D) -> (T, D),
+}
+
+impl<T, D> WeightedSpawn<T, D>
+where
+    T: Eq + Clone + Send + Sync + 'static,
+    D: Default + Clone + Send + Sync + 'static,
+{
+    /// Create a new event that picks one of the objects and spawns it with the default value of the user data.
+    pub fn new(choices: impl IntoIterator<Item = (T, f32)>) -> Self {
+        Self::with_data(choices, default())
+    }
+}
+
+impl<T, D> WeightedSpawn<T, D>
+where
+    T: Eq + Clone + Send + Sync + 'static,
+    D: Clone + Send + Sync + 'static,
+{
+    /// Create a new event that picks one of the objects and spawns it with the given user data.
+    pub fn with_data(choices: impl IntoIterator<Item = (T, f32)>, data: D) -> Self {
+        Self {
+            choices: choices.into_iter().collect(),
+            data,
+            count: 1,
+            unique: false,
+            correlation_id: None,
+            clone: |object, data| (object.clone(), data.clone()),
+        }
+    }
+
+    /// Pick `count` objects independently of each other, so the same object can be picked multiple times.
+    pub fn pick(mut self, count: usize) -> Self {
+        self.count = count;
+        self.unique = false;
+        self
+    }
+
+    /// Pick `count` different objects, i.e. without replacement.
+    ///
+    /// # Example
+    /// ```rust
+    /// use spew::prelude::*;
+    ///
+    /// #[derive(Debug, Eq, PartialEq, Clone)]
+    /// enum Loot {
+    ///    Sword,
+    ///    Shield,
+    ///    Potion,
+    /// }
+    ///
+    /// // Drops two different items
+    /// let drop: WeightedSpawn<Loot> = WeightedSpawn::new([(Loot::Sword, 1.0), (Loot::Shield, 1.0), (Loot::Potion, 5.0)]).pick_unique(2);
+    /// ```
+    pub fn pick_unique(mut self, count: usize) -> Self {
+        self.count = count;
+        self.unique = true;
+        self
+    }
+
+    /// Attach an id that is passed on to the [`SpawnEvent`] of every picked object.
+    pub fn correlation_id(mut self, id: u64) -> Self {
+        self.correlation_id = Some(id);
+        self
+    }
+}
+
+impl<T, D> WeightedSpawn<T, D>
+where
+    T: Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    /// Pick the objects and create their spawn events.
+    fn into_spawn_events(self, rng: &mut SpewRng) -> Vec<SpawnEvent<T, D>> {
+        let mut weights: Vec<_> = self.choices.iter().map(|(_, weight)| *weight).collect();
+        let mut events = Vec::with_capacity(self.count);
+        for _ in 0..self.count {
+            let Some(index) = rng.pick_weighted(weights.iter().copied()) else {
+                break;
+            };
+            if self.unique {
+                weights[index] = 0.0;
+            }
+            let (object, data) = (self.clone)(&self.choices[index].0, &self.data);
+            let mut event = SpawnEvent::with_data(object, data);
+            event.correlation_id = self.correlation_id;
+            events.push(event);
+        }
+        events
+    }
+}
+
+pub(crate) fn weighted_spawns<T, D>(
+    mut weighted_spawns: ResMut<Events<WeightedSpawn<T, D>>>,
+    mut rng: ResMut<SpewRng>,
+    mut spawn_events: EventWriter<SpawnEvent<T, D>>,
+) where
+    T: Eq + Send + Sync + 'static,
+    D: Send + Sync + 'static,
+{
+    for weighted_spawn in weighted_spawns.drain() {
+        spawn_events.send_batch(weighted_spawn.into_spawn_events(&mut rng));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Eq, PartialEq, Clone)]
+    enum Loot {
+        Sword,
+        Shield,
+        Potion,
+    }
+
+    fn picked_objects(weighted_spawn: WeightedSpawn<Loot>, rng: &mut SpewRng) -> Vec<Loot> {
+        weighted_spawn
+            .into_spawn_events(rng)
+            .into_iter()
+            .map(|event| event.object)
+            .collect()
+    }
+
+    #[test]
+    fn same_seed_same_numbers() {
+        let numbers = |seed| {
+            let mut rng = SpewRng::seeded(seed);
+            (0..8).map(|_| rng.next_u64()).collect::<Vec<_>>()
+        };
+        assert_eq!(numbers(42), numbers(42));
+        assert_ne!(numbers(42), numbers(43));
+        let mut rng = SpewRng::default();
+        assert!((0..1000)
+            .map(|_| rng.next_f64())
+            .all(|x| (0.0..1.0).contains(&x)));
+    }
+
+    #[test]
+    fn pick_proportionally_to_weights() {
+        let mut rng = SpewRng::seeded(7);
+        let mut picks = [0; 3];
+        for _ in 0..10_000 {
+            picks[rng.pick_weighted([1.0, 3.0, 0.0]).unwrap()] += 1;
+        }
+        assert_eq!(picks[2], 0);
+        let share = picks[1] as f64 / 10_000.0;
+        assert!((0.72..0.78).contains(&share), "{share}");
+    }
+
+    #[test]
+    fn never_pick_weights_that_are_not_positive() {
+        let mut rng = SpewRng::default();
+        for _ in 0..100 {
+            assert_eq!(
+                rng.pick_weighted([0.0, -1.0, f32::NAN, 2.0, f32::INFINITY]),
+                Some(3)
+            );
+        }
+        assert_eq!(rng.pick_weighted([0.0, -1.0]), None);
+        assert_eq!(rng.pick_weighted([]), None);
+    }
+
+    #[test]
+    fn same_seed_same_picks() {
+        let choices = [(Loot::Sword, 1.0), (Loot::Shield, 2.0), (Loot::Potion, 3.0)];
+        let picks = |seed| {
+            picked_objects(
+                WeightedSpawn::new(choices.clone()).pick(16),
+                &mut SpewRng::seeded(seed),
+            )
+        };
+        assert_eq!(picks(3).len(), 16);
+        assert_eq!(picks(3), picks(3));
+        assert_ne!(picks(3), picks(4));
+    }
+
+    #[test]
+    fn pick_repeatedly() {
+        let weighted_spawn = WeightedSpawn::new([
+            (Loot::Sword, 1.0),
+            (Loot::Shield, 0.0),
+            (Loot::Potion, -2.0),
+        ])
+        .pick(5);
+        assert_eq!(
+            picked_objects(weighted_spawn, &mut SpewRng::default()),
+            vec![Loot::Sword; 5]
+        );
+    }
+
+    #[test]
+    fn pick_unique_stops_when_nothing_is_left() {
+        let weighted_spawn =
+            WeightedSpawn::new([(Loot::Sword, 1.0), (Loot::Shield, 1.0), (Loot::Potion, 0.0)])
+                .pick_unique(5);
+        let mut picked = picked_objects(weighted_spawn, &mut SpewRng::default());
+        picked.sort_by_key(|loot| loot.clone() as u8);
+        assert_eq!(picked, vec![Loot::Sword, Loot::Shield]);
+
+        let weighted_spawn: WeightedSpawn<Loot> = WeightedSpawn::new([]).pick_unique(2);
+        assert!(picked_objects(weighted_spawn, &mut SpewRng::default()).is_empty());
+    }
+}
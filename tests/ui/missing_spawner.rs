@@ -0,0 +1,12 @@
+use spew::prelude::*;
+
+#[derive(Debug, Eq, PartialEq, Spewable)]
+enum Object {
+    #[spawner(spawn_cube)]
+    Cube,
+    Sphere,
+}
+
+fn spawn_cube() {}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use spew::prelude::*;
+
+#[derive(Debug, Eq, PartialEq, Spewable)]
+enum Object {
+    #[spawner(skip)]
+    #[spawner(spawn_cube)]
+    Cube,
+}
+
+fn spawn_cube() {}
+
+fn main() {}
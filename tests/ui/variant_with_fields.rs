@@ -0,0 +1,11 @@
+use spew::prelude::*;
+
+#[derive(Debug, Eq, PartialEq, Spewable)]
+enum Object {
+    #[spawner(spawn_cube)]
+    Cube { size: u32 },
+}
+
+fn spawn_cube() {}
+
+fn main() {}